@@ -0,0 +1,43 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    dump::{dot, sexp},
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::parse,
+    tokenizer::tokenize,
+};
+
+/// Dump mode: print the tree of every expression in the file (or stdin) that the parser builds,
+/// as a Graphviz graph (`dot`, one `digraph` per line) or as an S-expression (`sexp`). \
+/// `--dump-ast=<dot|sexp> [file]` (or `--dump-ast <dot|sexp> [file]`)
+pub(super) fn dump_mode(args: Vec<String>) {
+    let mut args = args.into_iter().skip(1);
+    let format = match args.next().as_deref() {
+        Some("--dump-ast") => args.next(),
+        Some(arg) => arg.strip_prefix("--dump-ast=").map(str::to_string),
+        None => None,
+    };
+    let dot_format = match format.as_deref() {
+        Some("dot") => true,
+        Some("sexp") => false,
+        _ => {
+            eprintln!("--dump-ast expects dot or sexp!");
+            std::process::exit(1);
+        }
+    };
+
+    let lines = match args.next() {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+    for (idx, line) in lines {
+        let tokens = tokenize(&line, idx);
+        let expression = parse(&tokens, idx);
+        if dot_format {
+            println!("{}", dot(&expression, &format!("line{}", idx + 1)));
+        } else {
+            println!("{}", sexp(&expression));
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::parse,
+    printer::{pretty, PrintConfig},
+    tokenizer::tokenize,
+};
+
+/// Format mode: print every expression in the file (or stdin) with the given layout. \
+/// `-f [--ascii] [--space] [--width N] [file]`
+pub(super) fn format_mode(args: Vec<String>) {
+    let mut config = PrintConfig::default();
+    let mut filename = None;
+
+    let mut args = args.into_iter().skip(2);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ascii" => config.unicode = false,
+            "--space" => config.dot = false,
+            "--width" => match args.next().and_then(|width| width.parse().ok()) {
+                Some(width) => config.width = Some(width),
+                None => {
+                    eprintln!("--width expects a number!");
+                    std::process::exit(1);
+                }
+            },
+            _ => filename = Some(arg),
+        }
+    }
+
+    let lines = match filename {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+    for (idx, line) in lines {
+        let tokens = tokenize(&line, idx);
+        let expression = parse(&tokens, idx);
+        println!("{}", pretty(&expression, &config));
+    }
+}
@@ -3,15 +3,47 @@
 
 // Import handy dbg! macro (shadowing std::dbg! macro)
 use crate::dbg;
+use crate::tokenizer::{is_blank, is_incomplete};
 
 use std::{
     fs::File,
     io::{stdin, BufRead, BufReader},
 };
 
+/// Group physical lines into expressions, paired with the index of the line they start on. \
+/// Blank and comment-only lines are skipped, and an unfinished expression
+/// (e.g. unbalanced parentheses) is joined with the lines that follow it
+fn logical_lines(lines: Vec<String>) -> Vec<(usize, String)> {
+    let mut logical = Vec::new();
+    let mut pending = String::new();
+    let mut start = 0;
+
+    for (idx, line) in lines.into_iter().enumerate() {
+        if pending.is_empty() {
+            start = idx;
+        } else {
+            pending.push('\n');
+        }
+        pending.push_str(&line);
+
+        if is_incomplete(&pending) {
+            continue;
+        }
+        if !is_blank(&pending) {
+            logical.push((start, pending));
+        }
+        pending = String::new();
+    }
+    // the input ended while the expression was unfinished, the tokenizer or parser reports it
+    if !pending.is_empty() {
+        logical.push((start, pending));
+    }
+    logical
+}
+
 /// Read lines from file
 /// Standard mode: read until EOF
-pub(super) fn read_lines_from_file(filename: &str) -> Vec<String> {
+pub(super) fn read_lines_from_file(filename: &str) -> Vec<(usize, String)> {
     // get os args input
 
     let mut lines = Vec::new();
@@ -27,6 +59,7 @@ pub(super) fn read_lines_from_file(filename: &str) -> Vec<String> {
     for line in reader.lines() {
         lines.push(line.expect("Could not read line"));
     }
+    let lines = logical_lines(lines);
 
     dbg!(&lines);
     lines
@@ -34,7 +67,7 @@ pub(super) fn read_lines_from_file(filename: &str) -> Vec<String> {
 
 /// Read lines from terminal
 /// Standard mode: read until EOF
-pub(super) fn read_lines_from_terminal() -> Vec<String> {
+pub(super) fn read_lines_from_terminal() -> Vec<(usize, String)> {
     // vec for storing lines
     let mut lines = Vec::new();
 
@@ -51,7 +84,7 @@ pub(super) fn read_lines_from_terminal() -> Vec<String> {
             }
         }
     }
-    lines
+    logical_lines(lines)
 }
 
 /// Read line from terminal
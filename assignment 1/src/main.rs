@@ -18,10 +18,19 @@ use bench::bench;
 mod manual_mode;
 use manual_mode::manual_mode;
 
+mod printer;
+
+mod format_mode;
+use format_mode::format_mode;
+
+mod dump;
+mod dump_mode;
+use dump_mode::dump_mode;
+
 #[allow(dead_code, unused)]
 fn main() {
     // if args provided
-    let lines: Vec<String>;
+    let lines: Vec<(usize, String)>;
     if std::env::args().len() > 1 {
         // get args
         let args = std::env::args().collect::<Vec<String>>();
@@ -35,6 +44,14 @@ fn main() {
                 bench(args);
                 return;
             }
+            "-f" => {
+                format_mode(args);
+                return;
+            }
+            arg if arg.starts_with("--dump-ast") => {
+                dump_mode(args);
+                return;
+            }
             arg => {
                 lines = read_lines_from_file(arg);
             }
@@ -50,7 +67,6 @@ fn main() {
 
     let expressions: Vec<Expression> = lines
         .into_iter()
-        .enumerate()
         .map(|(idx, line)| {
             let tokens = tokenize(&line, idx);
             let expression = parse(&tokens, idx);
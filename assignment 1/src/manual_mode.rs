@@ -4,7 +4,9 @@
 use std::io::{self, Write};
 
 use crate::{
-    line_reader::read_line_from_terminal, parser::manual_parse, tokenizer::manual_tokenize,
+    line_reader::read_line_from_terminal,
+    parser::manual_parse,
+    tokenizer::{is_blank, manual_tokenize},
 };
 
 pub(super) fn manual_mode() {
@@ -14,6 +16,10 @@ pub(super) fn manual_mode() {
         print!("Expression: ");
         io::stdout().flush().unwrap();
         let input = read_line_from_terminal();
+        // nothing to parse on blank or comment-only input
+        if is_blank(&input) {
+            continue;
+        }
         let tokens = manual_tokenize(&input);
         if tokens.is_none() {
             continue;
@@ -3,6 +3,7 @@
 
 // Import handy dbg! macro (shadowing std::dbg! macro)
 use crate::dbg;
+use crate::printer::{pretty, PrintConfig};
 use crate::tokenizer::Token;
 
 use std::fmt::{Display, Formatter, Result};
@@ -70,11 +71,7 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
                                     break;
                                 }
                             }
-                            Token::Variable(_) => {
-                                if paren_count == 0 {
-                                    break;
-                                }
-                            }
+                            Token::Variable(_) if paren_count == 0 => break,
                             _ => {}
                         }
                         end_idx += 1;
@@ -147,64 +144,12 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
     }
 }
 
-/// Display the expression in the normal format
+/// Display the expression in the normal format,
+/// with as few parentheses as possible (see printer.rs)
 /// (Used by e.g. .to_string() and .print() functions)
 impl Display for Expression {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
-        match self {
-            /*
-            If Lambda -> print λ{name}.{expr}
-            Lambda(
-                "x",
-                Application ([
-                    Variable("a"),
-                    Variable("b")
-                ])
-            )
-
-            Becomes
-
-            λx.a b
-            */
-            Expression::Abstraction(name, expr) => {
-                write!(fmt, "λ{name}.{expr}")
-            }
-            /*
-            If Variable -> print {name}
-            Variable("a")
-
-            Becomes
-
-            a
-             */
-            Expression::Variable(name) => write!(fmt, "{name}"),
-            /*
-            If Application
-                If lexpr = abs | app -> "({left_expr})"
-                Else -> "{left_expr}"
-                + " "
-                + If rexpr = app | abs -> "({right_expr})"
-                Else -> "{right_expr}"
-             */
-            Expression::Application(left_expr, right_expr) => {
-                // Left
-                if let Expression::Variable(_) = left_expr.as_ref() {
-                    write!(fmt, "{left_expr}")
-                } else {
-                    write!(fmt, "({left_expr})")
-                }?;
-                // Separator
-                write!(fmt, " ")?;
-                // Right
-                match right_expr.as_ref() {
-                    Expression::Application(_, _) | Expression::Abstraction(_, _) => {
-                        write!(fmt, "({right_expr})")
-                    }
-
-                    r_expr => write!(fmt, "{r_expr}"),
-                }
-            }
-        }
+        write!(fmt, "{}", pretty(self, &PrintConfig::default()))
     }
 }
 
@@ -0,0 +1,197 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::parser::Expression;
+
+/// How expressions are printed
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PrintConfig {
+    /// Print abstractions with `λ` (true) or with `\` (false)
+    pub(crate) unicode: bool,
+    /// Separate the bound variable and the body with a dot (true) or with a space (false)
+    pub(crate) dot: bool,
+    /// Target line width, `None` prints everything on one line
+    pub(crate) width: Option<usize>,
+}
+
+impl Default for PrintConfig {
+    fn default() -> Self {
+        PrintConfig {
+            unicode: true,
+            dot: true,
+            width: None,
+        }
+    }
+}
+
+/// A document in the style of Wadler's "A prettier printer". \
+/// A group is printed on one line if it fits in the remaining width,
+/// otherwise its breaks become newlines.
+#[derive(Debug)]
+enum Doc {
+    /// Literal text, never contains newlines
+    Text(String),
+    /// A possible line break, printed as the given text when its group is flat
+    Break(&'static str),
+    /// Increase the indentation of the breaks in the document
+    Nest(usize, Box<Doc>),
+    /// Documents printed after each other
+    Concat(Vec<Doc>),
+    /// Print the document flat if it fits, else break it
+    Group(Box<Doc>),
+}
+
+fn text(s: impl Into<String>) -> Doc {
+    Doc::Text(s.into())
+}
+
+fn parens(doc: Doc) -> Doc {
+    Doc::Concat(vec![text("("), doc, text(")")])
+}
+
+/// Whether the items (flattened from the given one onwards) fit in the remaining width,
+/// up to the first line break that is not flat
+fn fits(mut remaining: isize, items: &[(usize, bool, &Doc)]) -> bool {
+    let mut stack = items.to_vec();
+    while let Some((indent, flat, doc)) = stack.pop() {
+        if remaining < 0 {
+            return false;
+        }
+        match doc {
+            Doc::Text(s) => remaining -= s.chars().count() as isize,
+            Doc::Break(s) => {
+                if !flat {
+                    return true;
+                }
+                remaining -= s.chars().count() as isize;
+            }
+            Doc::Nest(i, doc) => stack.push((indent + i, flat, doc)),
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc))),
+            Doc::Group(doc) => stack.push((indent, flat, doc)),
+        }
+    }
+    remaining >= 0
+}
+
+/// Lay the document out within the given width
+fn render(doc: &Doc, width: Option<usize>) -> String {
+    let mut out = String::new();
+    let mut column = 0;
+    // (indentation, flat, document), the top of the stack is printed first
+    let mut stack = vec![(0, width.is_none(), doc)];
+
+    while let Some((indent, flat, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(s) => {
+                out.push_str(s);
+                column += s.chars().count();
+            }
+            Doc::Break(s) => {
+                if flat {
+                    out.push_str(s);
+                    column += s.chars().count();
+                } else {
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indent));
+                    column = indent;
+                }
+            }
+            Doc::Nest(i, doc) => stack.push((indent + i, flat, doc)),
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc))),
+            Doc::Group(doc) => {
+                let remaining = width.unwrap_or(usize::MAX) as isize - column as isize;
+                let mut items = stack.clone();
+                items.push((indent, true, doc));
+                let flat = flat || fits(remaining, &items);
+                stack.push((indent, flat, doc));
+            }
+        }
+    }
+    out
+}
+
+/// Build the document of an expression, with as few parentheses as the parser allows.
+///
+/// Application associates to the left, so only an application on the right needs parentheses. \
+/// With a dot the body of an abstraction extends as far right as possible,
+/// so an abstraction needs parentheses unless nothing follows it (`rightmost`). \
+/// Without a dot the body is a single variable, abstraction or parenthesised expression,
+/// so an abstraction never needs parentheses, but an application as its body does.
+fn expression_doc(expr: &Expression, rightmost: bool, config: &PrintConfig) -> Doc {
+    match expr {
+        Expression::Variable(name) => text(name),
+        Expression::Abstraction(_, _) => {
+            // a chain of abstractions shares one line: λx.λy.λz.
+            let lambda = if config.unicode { "λ" } else { "\\" };
+            let separator = if config.dot { "." } else { " " };
+            let mut binders = String::new();
+            let mut body = expr;
+            while let Expression::Abstraction(name, inner) = body {
+                binders.push_str(&format!("{lambda}{name}{separator}"));
+                body = inner;
+            }
+            // the break takes over the role of the space separator
+            if !config.dot {
+                binders.pop();
+            }
+
+            let body_doc = match body {
+                Expression::Application(_, _) if !config.dot => {
+                    parens(expression_doc(body, true, config))
+                }
+                _ => expression_doc(body, true, config),
+            };
+            let doc = Doc::Group(Box::new(Doc::Concat(vec![
+                text(binders),
+                Doc::Nest(
+                    2,
+                    Box::new(Doc::Concat(vec![
+                        Doc::Break(if config.dot { "" } else { " " }),
+                        body_doc,
+                    ])),
+                ),
+            ])));
+            if config.dot && !rightmost {
+                parens(doc)
+            } else {
+                doc
+            }
+        }
+        Expression::Application(_, _) => {
+            // flatten the left-associative spine: ((f a) b) c = f [a, b, c]
+            let mut args = Vec::new();
+            let mut head = expr;
+            while let Expression::Application(left_expr, right_expr) = head {
+                args.push(right_expr.as_ref());
+                head = left_expr;
+            }
+            args.reverse();
+
+            let mut rest = Vec::new();
+            let last = args.len() - 1;
+            for (i, arg) in args.into_iter().enumerate() {
+                rest.push(Doc::Break(" "));
+                rest.push(match arg {
+                    Expression::Application(_, _) => parens(expression_doc(arg, true, config)),
+                    _ => expression_doc(arg, rightmost && i == last, config),
+                });
+            }
+            Doc::Group(Box::new(Doc::Concat(vec![
+                expression_doc(head, false, config),
+                Doc::Nest(2, Box::new(Doc::Concat(rest))),
+            ])))
+        }
+    }
+}
+
+/// Print the expression according to the given configuration
+pub(crate) fn pretty(expr: &Expression, config: &PrintConfig) -> String {
+    let doc = expression_doc(expr, true, config);
+    let out = render(&doc, config.width);
+    // a broken layout is wrapped in parentheses, so its lines are read back as one expression
+    if out.contains('\n') {
+        render(&parens(doc), config.width)
+    } else {
+        out
+    }
+}
@@ -4,6 +4,8 @@
 // Import handy dbg! macro (shadowing std::dbg! macro)
 use crate::dbg;
 use std::fmt::{Display, Formatter, Result};
+use std::iter::{Enumerate, Peekable};
+use std::str::Chars;
 
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) enum Token {
@@ -29,6 +31,7 @@ pub(super) enum LexError {
     InvalidLambdaVariableChar(char, usize),
     EmptyLambdaVariable(usize),
     TrailingDot(usize),
+    UnterminatedComment(usize),
 }
 
 impl Display for LexError {
@@ -45,12 +48,88 @@ impl Display for LexError {
                 write!(f, "Invalid lambda body character: '{}' at pos: {}", c, i)
             }
             LexError::TrailingDot(i) => write!(f, "Trailing dot at pos: {}", i),
+            LexError::UnterminatedComment(i) => {
+                write!(f, "Unterminated block comment starting at pos: {}", i)
+            }
+        }
+    }
+}
+
+impl LexError {
+    /// The position (1-based character index) the error occurred at
+    fn pos_mut(&mut self) -> &mut usize {
+        match self {
+            LexError::EmptyVariableName(i)
+            | LexError::InvalidCharacter(_, i)
+            | LexError::InvalidExpression(i)
+            | LexError::InvalidVariableName(i)
+            | LexError::InvalidLambdaVariableChar(_, i)
+            | LexError::EmptyLambdaVariable(i)
+            | LexError::TrailingDot(i)
+            | LexError::UnterminatedComment(i) => i,
         }
     }
 }
 
 type LexResult<T> = std::result::Result<T, LexError>;
 
+type CharStream<'a> = Peekable<Enumerate<Chars<'a>>>;
+
+/// Skip a line comment (`#` or `--`), up to (but not including) the next newline
+fn skip_line_comment(chars: &mut CharStream) {
+    while let Some((_, c)) = chars.peek() {
+        if *c == '\n' {
+            break;
+        }
+        chars.next();
+    }
+}
+
+/// Skip a block comment `{- ... -}`, block comments can be nested. \
+/// The opening '{' has already been consumed, `start` is its index
+fn skip_block_comment(chars: &mut CharStream, start: usize) -> LexResult<()> {
+    // consume the '-' of the opening delimiter
+    chars.next();
+    let mut depth = 1;
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '{' if matches!(chars.peek(), Some((_, '-'))) => {
+                chars.next();
+                depth += 1;
+            }
+            '-' if matches!(chars.peek(), Some((_, '}'))) => {
+                chars.next();
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(LexError::UnterminatedComment(start + 1))
+}
+
+/// Whether a comment (`#`, `--` or `{-`) starts at the next character
+fn comment_ahead(chars: &CharStream) -> bool {
+    let mut ahead = chars.clone().map(|(_, c)| c);
+    matches!(
+        (ahead.next(), ahead.next()),
+        (Some('#'), _) | (Some('-'), Some('-')) | (Some('{'), Some('-'))
+    )
+}
+
+/// Skip the comment that starts at the next character (see `comment_ahead`)
+fn skip_comment(chars: &mut CharStream) -> LexResult<()> {
+    match chars.next() {
+        Some((start, '{')) => skip_block_comment(chars, start),
+        _ => {
+            skip_line_comment(chars);
+            Ok(())
+        }
+    }
+}
+
 fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
     let mut tokens = Vec::with_capacity(input.len());
     let mut chars = input.chars().enumerate().peekable();
@@ -60,7 +139,18 @@ fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
             '\\' | 'λ' => {
                 // retrieve lambda variable
                 let mut varname = String::new();
-                while let Some((idx, c)) = chars.peek() {
+                loop {
+                    // a comment ends the variable name, or is skipped if the name has not started yet
+                    if comment_ahead(&chars) {
+                        if !varname.is_empty() {
+                            break;
+                        }
+                        skip_comment(&mut chars)?;
+                        continue;
+                    }
+                    let Some((idx, c)) = chars.peek() else {
+                        break;
+                    };
                     match c {
                         // a dot, a lambda and a left parenthesis always signify the end of the variable name
                         '.' | '(' | '\\' | 'λ' => {
@@ -106,6 +196,11 @@ fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
 
             '(' => tokens.push(Token::LParen),
             ')' => tokens.push(Token::RParen),
+
+            // comments are ignored
+            '#' => skip_line_comment(&mut chars),
+            '-' if matches!(chars.peek(), Some((_, '-'))) => skip_line_comment(&mut chars),
+            '{' if matches!(chars.peek(), Some((_, '-'))) => skip_block_comment(&mut chars, idx)?,
             '.' => {
                 // check if there is something after the dot
                 let mut found = false;
@@ -150,6 +245,24 @@ fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
     Ok(tokens)
 }
 
+/// Errors are positioned relative to the start of the input, which can span multiple lines
+/// (e.g. a block comment that is continued on the next line). \
+/// Rewrites the position of the error to the column on its own line,
+/// and returns the number of lines before that line
+fn relocate(input: &str, err_code: &mut LexError) -> usize {
+    let pos = err_code.pos_mut();
+    let mut line = 0;
+    let mut column = *pos;
+    for (i, c) in input.chars().enumerate().take(pos.saturating_sub(1)) {
+        if c == '\n' {
+            line += 1;
+            column = *pos - (i + 1);
+        }
+    }
+    *pos = column;
+    line
+}
+
 /// Parse the given string into a vector of tokens
 /// If the given string is not a valid expression, prints an error and exits
 ///
@@ -167,11 +280,13 @@ pub(crate) fn tokenize(input: &str, idx: usize) -> Vec<Token> {
     dbg!(&tokens);
     match tokens {
         // If error in a token, print error and exit
-        Err(err_code) => {
+        Err(mut err_code) => {
+            // the input can span multiple physical lines, so point at the line the error is on
+            let line = relocate(input, &mut err_code);
             eprintln!(
                 "Invalid expression [{}] caught during tokenizing on line {}!",
                 err_code,
-                idx + 1
+                idx + line + 1
             );
             std::process::exit(1);
         }
@@ -208,3 +323,80 @@ pub(crate) fn manual_tokenize(input: &str) -> Option<Vec<Token>> {
         Ok(tokens) => tokens.into(),
     }
 }
+
+/// Whether the given input contains nothing but whitespace and comments
+pub(crate) fn is_blank(input: &str) -> bool {
+    matches!(_tokenize(input), Ok(tokens) if tokens.is_empty())
+}
+
+/// Whether the given input is unfinished and continues on the next line:
+/// a block comment or a parenthesis is still open, or it ends with a lambda or a dot
+pub(crate) fn is_incomplete(input: &str) -> bool {
+    match _tokenize(input) {
+        Err(LexError::UnterminatedComment(_)) | Err(LexError::TrailingDot(_)) => true,
+        Err(_) => false,
+        Ok(tokens) => {
+            let mut paren_count = 0;
+            for token in &tokens {
+                match token {
+                    Token::LParen => paren_count += 1,
+                    Token::RParen => paren_count -= 1,
+                    _ => {}
+                }
+            }
+            if paren_count > 0 {
+                return true;
+            }
+            // an abstraction still needs its body
+            matches!(tokens.last(), Some(Token::Lambda(_) | Token::Dot))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lambda(var: &str) -> Token {
+        Token::Lambda(var.to_string())
+    }
+
+    fn variable(var: &str) -> Token {
+        Token::Variable(var.to_string())
+    }
+
+    #[test]
+    fn line_comment_after_binder() {
+        assert_eq!(_tokenize("\\x#c"), Ok(vec![lambda("x")]));
+        assert_eq!(_tokenize("\\x--c"), Ok(vec![lambda("x")]));
+        assert_eq!(
+            _tokenize("λx # c\n.x"),
+            Ok(vec![lambda("x"), Token::Dot, variable("x")])
+        );
+    }
+
+    #[test]
+    fn block_comment_after_binder() {
+        assert_eq!(
+            _tokenize("\\x{- c -} x"),
+            Ok(vec![lambda("x"), variable("x")])
+        );
+        assert_eq!(
+            _tokenize("\\x{- c -}.x"),
+            Ok(vec![lambda("x"), Token::Dot, variable("x")])
+        );
+    }
+
+    #[test]
+    fn block_comment_before_binder_name() {
+        assert_eq!(
+            _tokenize("\\{- c -} x.x"),
+            Ok(vec![lambda("x"), Token::Dot, variable("x")])
+        );
+    }
+
+    #[test]
+    fn line_comment_after_variable() {
+        assert_eq!(_tokenize("a#c"), Ok(vec![variable("a")]));
+    }
+}
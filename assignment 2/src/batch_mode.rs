@@ -0,0 +1,197 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    line_reader::{batch_read_lines_from_file, batch_read_lines_from_terminal},
+    parser::{_parse, ParseError},
+    reducer::{batch_reduce, ReduceError},
+    tokenizer::{batch_tokenize, LexError},
+};
+
+/// Why a line failed, in the order of the phases
+enum Failure {
+    /// The error and the offset of the line it is on
+    Lex(LexError, usize),
+    Parse(ParseError),
+    /// The expression printed differently after reparsing it
+    Reparse(String, String),
+    Reduce(ReduceError),
+}
+
+/// Tokenize, parse, reparse and reduce the line, like normal mode, but return the failure instead of exiting
+fn process(line: &str) -> Result<String, Failure> {
+    let tokens = batch_tokenize(line).map_err(|(err, offset)| Failure::Lex(err, offset))?;
+    let expression = _parse(&tokens).map_err(Failure::Parse)?;
+
+    // reparse the expression
+    let exprstring = expression.to_string();
+    let tokens2 = batch_tokenize(&exprstring).map_err(|(err, offset)| Failure::Lex(err, offset))?;
+    let expression2 = _parse(&tokens2).map_err(Failure::Parse)?;
+    if expression != expression2 {
+        return Err(Failure::Reparse(
+            expression2.to_string(),
+            expression.to_string(),
+        ));
+    }
+
+    batch_reduce(expression2)
+        .map(|expr| expr.to_string())
+        .map_err(Failure::Reduce)
+}
+
+/// The number of lines that reduced, of the lines that failed in every phase,
+/// and of the files that could not be read
+#[derive(Debug, Default)]
+struct Summary {
+    ok: usize,
+    lex: usize,
+    parse: usize,
+    reduce: usize,
+    input: usize,
+}
+
+/// Reduce every line of the input named `name`, printing the normal form or error of every line,
+/// and count them in the summary. An input that could not be read counts as one failed input
+fn reduce_input(name: &str, lines: std::io::Result<Vec<(usize, String)>>, summary: &mut Summary) {
+    let lines = match lines {
+        Ok(lines) => lines,
+        Err(error) => {
+            summary.input += 1;
+            eprintln!("{}: Error opening file: {}", name, error);
+            return;
+        }
+    };
+    for (idx, line) in lines {
+        match process(&line) {
+            Ok(normal_form) => {
+                summary.ok += 1;
+                println!("{}:{}: {}", name, idx + 1, normal_form);
+            }
+            Err(Failure::Lex(err_code, offset)) => {
+                summary.lex += 1;
+                eprintln!(
+                    "{}:{}: Invalid expression [{}] caught during tokenizing!",
+                    name,
+                    idx + offset + 1,
+                    err_code
+                );
+            }
+            Err(Failure::Parse(err_code)) => {
+                summary.parse += 1;
+                eprintln!(
+                    "{}:{}: Invalid expression [{}] caught during parsing!",
+                    name,
+                    idx + 1,
+                    err_code
+                );
+            }
+            Err(Failure::Reparse(reparsed, parsed)) => {
+                summary.parse += 1;
+                eprintln!(
+                    "{}:{}: Invalid expression (on reparse) '{}' is not equal to '{}'!",
+                    name,
+                    idx + 1,
+                    reparsed,
+                    parsed
+                );
+            }
+            Err(Failure::Reduce(err)) => {
+                summary.reduce += 1;
+                eprintln!(
+                    "{}:{}: Error [{}] caught during reducing!",
+                    name,
+                    idx + 1,
+                    err
+                );
+            }
+        }
+    }
+}
+
+/// Reduce every line of every file, or of stdin if there are no files (see batch mode)
+fn batch(filenames: &[String]) -> Summary {
+    let mut summary = Summary::default();
+    if filenames.is_empty() {
+        reduce_input("<stdin>", batch_read_lines_from_terminal(), &mut summary);
+    }
+    for filename in filenames {
+        reduce_input(filename, batch_read_lines_from_file(filename), &mut summary);
+    }
+    summary
+}
+
+/// Batch mode: reduce every line of every file (or stdin), continuing past lines and files that fail. \
+/// Every physical line is an expression of its own (unless a block comment continues on the next line),
+/// so the counts add up to the number of non-blank lines. \
+/// Prints every normal form (stdout) or error (stderr) prefixed with `file:line:`, and a summary at the end. \
+/// Exits with code 1 if a file could not be read or a line could not be tokenized or parsed,
+/// with code 2 if a line could not be reduced, and with code 0 otherwise. \
+/// `-l [file]...`
+pub(super) fn batch_mode(args: Vec<String>) {
+    let summary = batch(&args[2..]);
+
+    println!(
+        "{} ok / {} lex errors / {} parse errors / {} reduction failures / {} unreadable files",
+        summary.ok, summary.lex, summary.parse, summary.reduce, summary.input
+    );
+    if summary.input > 0 || summary.lex > 0 || summary.parse > 0 {
+        std::process::exit(1);
+    }
+    if summary.reduce > 0 {
+        std::process::exit(2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write the contents to a file in the temporary directory, returns its path
+    fn temp_file(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("batch_mode_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn every_physical_line_is_counted() {
+        // unbalanced parentheses are not joined with the next line
+        let file = temp_file("lines.txt", "(a\nb)\n\n# comment\nx\n(\\x x) y\n");
+        let summary = batch(&[file]);
+        assert_eq!(summary.ok, 2);
+        assert_eq!(summary.parse, 2);
+        assert_eq!(summary.lex + summary.reduce + summary.input, 0);
+    }
+
+    #[test]
+    fn block_comment_joins_lines() {
+        let file = temp_file("comment.txt", "{- a\n b -} z\nx\n");
+        let summary = batch(&[file]);
+        assert_eq!(summary.ok, 2);
+        assert_eq!(
+            summary.lex + summary.parse + summary.reduce + summary.input,
+            0
+        );
+    }
+
+    #[test]
+    fn unfinished_abstraction_is_a_parse_error() {
+        // used to panic in the parser, which aborted the whole batch
+        let file = temp_file("unfinished.txt", "a\n\\x (\nb\n");
+        let summary = batch(&[file]);
+        assert_eq!(summary.ok, 2);
+        assert_eq!(summary.parse, 1);
+    }
+
+    #[test]
+    fn missing_file_is_a_failed_input() {
+        let file = temp_file("present.txt", "x\ny\n");
+        let missing = std::env::temp_dir()
+            .join("batch_mode_missing_file.txt")
+            .to_string_lossy()
+            .into_owned();
+        let summary = batch(&[missing, file]);
+        assert_eq!(summary.input, 1);
+        assert_eq!(summary.ok, 2);
+    }
+}
@@ -0,0 +1,97 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::parser::Expression;
+
+/// A Graphviz graph that is built up one node and edge at a time
+struct Dot {
+    lines: Vec<String>,
+    nodes: usize,
+}
+
+impl Dot {
+    /// Add a node with the label and extra attributes (e.g. `shape=box`), returns its id
+    fn node(&mut self, label: &str, attributes: &str) -> usize {
+        let id = self.nodes;
+        self.nodes += 1;
+        let label = label.replace('\\', "\\\\").replace('"', "\\\"");
+        match attributes {
+            "" => self.lines.push(format!("  n{} [label=\"{}\"];", id, label)),
+            _ => self
+                .lines
+                .push(format!("  n{} [label=\"{}\", {}];", id, label, attributes)),
+        }
+        id
+    }
+
+    /// Add an edge from a node to its child
+    fn edge(&mut self, parent: usize, child: usize) {
+        self.lines.push(format!("  n{} -> n{};", parent, child));
+    }
+
+    /// Add a dashed edge from a binder to a variable it binds, which does not affect the layout
+    fn binding(&mut self, binder: usize, occurrence: usize) {
+        self.lines.push(format!(
+            "  n{} -> n{} [style=dashed, color=blue, constraint=false];",
+            binder, occurrence
+        ));
+    }
+}
+
+/// Add the nodes of the expression, returns the id of its root. \
+/// `scope` holds the bound variables with the abstraction that binds them, the innermost last
+fn expression_dot(dot: &mut Dot, expr: &Expression, scope: &mut Vec<(String, usize)>) -> usize {
+    match expr {
+        Expression::Variable(var) => {
+            match scope.iter().rev().find(|(name, _)| name == var) {
+                Some(&(_, binder)) => {
+                    let id = dot.node(var, "");
+                    dot.binding(binder, id);
+                    id
+                }
+                // a free variable
+                None => dot.node(var, "color=red"),
+            }
+        }
+        Expression::Application(lexpr, rexpr) => {
+            let id = dot.node("@", "");
+            let left = expression_dot(dot, lexpr, scope);
+            let right = expression_dot(dot, rexpr, scope);
+            dot.edge(id, left);
+            dot.edge(id, right);
+            id
+        }
+        Expression::Abstraction(var, body) => {
+            let id = dot.node(&format!("λ{}", var), "");
+            scope.push((var.clone(), id));
+            let body = expression_dot(dot, body, scope);
+            scope.pop();
+            dot.edge(id, body);
+            id
+        }
+    }
+}
+
+/// The expression as a Graphviz graph with the given name: applications are `@` nodes,
+/// abstractions `λx` nodes, free variables are red, and every bound variable has a dashed edge from its abstraction
+pub(crate) fn dot(expr: &Expression, name: &str) -> String {
+    let mut dot = Dot {
+        lines: Vec::new(),
+        nodes: 0,
+    };
+    expression_dot(&mut dot, expr, &mut Vec::new());
+    format!(
+        "digraph {} {{\n  ordering=out;\n{}\n}}",
+        name,
+        dot.lines.join("\n")
+    )
+}
+
+/// The expression as an S-expression, e.g. `λx.x y` is `(λ x (@ x y))`
+pub(crate) fn sexp(expr: &Expression) -> String {
+    match expr {
+        Expression::Variable(var) => var.clone(),
+        Expression::Application(lexpr, rexpr) => format!("(@ {} {})", sexp(lexpr), sexp(rexpr)),
+        Expression::Abstraction(var, body) => format!("(λ {} {})", var, sexp(body)),
+    }
+}
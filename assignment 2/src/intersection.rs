@@ -0,0 +1,376 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+// Import handy dbg! macro (shadowing std::dbg! macro)
+use crate::dbg;
+use crate::parser::{_parse, Expression, ParseError};
+use crate::tokenizer::Token;
+
+use std::fmt::{Display, Formatter, Result};
+
+/// An intersection type
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum Type {
+    /// <Variable>
+    Variable(String),
+    /// <Type> -> <Type>
+    Arrow(Box<Type>, Box<Type>),
+    /// <Type> ∧ <Type>, a term of this type has both types
+    Intersection(Box<Type>, Box<Type>),
+}
+
+/// An untyped term, where a bound variable can be given the type it is used at
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum Term {
+    /// <Variable>
+    Variable(String),
+    /// λ <Variable> ^ <Type> . <Term>, where the type is optional
+    Abstraction(String, Option<Type>, Box<Term>),
+    /// <Term> <Term>
+    Application(Box<Term>, Box<Term>),
+}
+
+/// The types of the free variables of a term
+pub(crate) type TypeContext = Vec<(String, Type)>;
+
+/// A line of an intersection type file
+#[derive(Debug)]
+pub(crate) enum Line {
+    /// <Context> ⊢ <Term> : <Type>, where the context is optional
+    Judgement(TypeContext, Term, Type),
+    /// <Expression>, without any types, of which a typing is searched for
+    Search(Expression),
+}
+
+#[derive(Debug)]
+enum IntersectionParseError {
+    Expression(ParseError),
+    EmptyType,
+    InvalidType,
+    UnexpectedRParen,
+    UnclosedLParen,
+    InvalidAnnotation,
+    InvalidContext,
+    TooManyTurnstiles,
+    TooManyColons,
+    NoType,
+}
+
+impl Display for IntersectionParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            IntersectionParseError::Expression(err_code) => write!(f, "{}", err_code),
+            IntersectionParseError::EmptyType => write!(f, "Empty type"),
+            IntersectionParseError::InvalidType => {
+                write!(
+                    f,
+                    "Invalid type, types are built from variables, ∧ (or /\\) and ->"
+                )
+            }
+            IntersectionParseError::UnexpectedRParen => write!(f, "Unexpected right parenthesis"),
+            IntersectionParseError::UnclosedLParen => write!(f, "Unclosed left parenthesis"),
+            IntersectionParseError::InvalidAnnotation => {
+                write!(
+                    f,
+                    "Invalid type of bound variable, should be: λ<variable>^<type>.<term>"
+                )
+            }
+            IntersectionParseError::InvalidContext => {
+                write!(
+                    f,
+                    "Invalid context, should be: <variable> : <type>, ... ⊢ <term> : <type>"
+                )
+            }
+            IntersectionParseError::TooManyTurnstiles => write!(f, "Too many turnstiles"),
+            IntersectionParseError::TooManyColons => write!(f, "Too many colons"),
+            IntersectionParseError::NoType => {
+                write!(
+                    f,
+                    "Missing type of a term with a context or typed variables, should be: <term> : <type>"
+                )
+            }
+        }
+    }
+}
+
+type IntersectionParseResult<T> = std::result::Result<T, IntersectionParseError>;
+
+/// The index of the first token outside of parentheses that is equal to the separator
+fn find_top_level(tokens: &[Token], separator: &Token) -> IntersectionParseResult<Option<usize>> {
+    let mut paren_count = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LParen => paren_count += 1,
+            Token::RParen if paren_count == 0 => {
+                return Err(IntersectionParseError::UnexpectedRParen)
+            }
+            Token::RParen => paren_count -= 1,
+            token if paren_count == 0 && token == separator => return Ok(Some(idx)),
+            _ => {}
+        }
+    }
+    match paren_count {
+        0 => Ok(None),
+        _ => Err(IntersectionParseError::UnclosedLParen),
+    }
+}
+
+/// The index of the right parenthesis that closes the left parenthesis at the start of the tokens
+fn closing_paren(tokens: &[Token]) -> IntersectionParseResult<usize> {
+    let mut paren_count = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LParen => paren_count += 1,
+            Token::RParen => {
+                paren_count -= 1;
+                if paren_count == 0 {
+                    return Ok(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(IntersectionParseError::UnclosedLParen)
+}
+
+/// Parse a type, the arrow associates to the right and binds weaker than ∧
+fn _parse_type(tokens: &[Token]) -> IntersectionParseResult<Type> {
+    match find_top_level(tokens, &Token::Arrow)? {
+        Some(arrow) => Ok(Type::Arrow(
+            Box::new(_parse_intersection(&tokens[..arrow])?),
+            Box::new(_parse_type(&tokens[arrow + 1..])?),
+        )),
+        None => _parse_intersection(tokens),
+    }
+}
+
+/// <Atom> ∧ <Atom> ∧ ..., associating to the right
+fn _parse_intersection(tokens: &[Token]) -> IntersectionParseResult<Type> {
+    match find_top_level(tokens, &Token::Intersection)? {
+        Some(intersection) => Ok(Type::Intersection(
+            Box::new(_parse_atomic_type(&tokens[..intersection])?),
+            Box::new(_parse_intersection(&tokens[intersection + 1..])?),
+        )),
+        None => _parse_atomic_type(tokens),
+    }
+}
+
+/// A type variable, or a type between parentheses
+fn _parse_atomic_type(tokens: &[Token]) -> IntersectionParseResult<Type> {
+    match tokens {
+        [] => Err(IntersectionParseError::EmptyType),
+        [Token::Variable(var)] => Ok(Type::Variable(var.clone())),
+        [Token::LParen, ..] if closing_paren(tokens)? == tokens.len() - 1 => {
+            _parse_type(&tokens[1..tokens.len() - 1])
+        }
+        _ => Err(IntersectionParseError::InvalidType),
+    }
+}
+
+/// Remove the types of the bound variables from the tokens, so the rest can be parsed as an untyped expression. \
+/// Returns the remaining tokens, and the types of the lambdas in the order they occur in (None if a lambda has no type)
+fn strip_annotations(tokens: &[Token]) -> IntersectionParseResult<(Vec<Token>, Vec<Option<Type>>)> {
+    let mut stripped = Vec::with_capacity(tokens.len());
+    let mut annotations = Vec::new();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        match (&tokens[idx], tokens.get(idx + 1)) {
+            (Token::Lambda(_), Some(Token::Hat)) => {
+                // the type is an atom, so a compound type needs parentheses
+                let end = match tokens.get(idx + 2) {
+                    Some(Token::Variable(_)) => idx + 2,
+                    Some(Token::LParen) => idx + 2 + closing_paren(&tokens[idx + 2..])?,
+                    _ => return Err(IntersectionParseError::InvalidAnnotation),
+                };
+                stripped.push(tokens[idx].clone());
+                annotations.push(Some(_parse_atomic_type(&tokens[idx + 2..=end])?));
+                idx = end;
+            }
+            (Token::Lambda(_), _) => {
+                stripped.push(tokens[idx].clone());
+                annotations.push(None);
+            }
+            (Token::Hat, _) => return Err(IntersectionParseError::InvalidAnnotation),
+            (token, _) => stripped.push(token.clone()),
+        }
+        idx += 1;
+    }
+    Ok((stripped, annotations))
+}
+
+/// Give the abstractions of the expression their types, in the order their lambdas occur in
+fn annotate(expression: Expression, annotations: &mut impl Iterator<Item = Option<Type>>) -> Term {
+    match expression {
+        Expression::Variable(var) => Term::Variable(var),
+        Expression::Abstraction(var, body) => {
+            let typ = annotations.next().flatten();
+            Term::Abstraction(var, typ, Box::new(annotate(*body, annotations)))
+        }
+        Expression::Application(left_expr, right_expr) => {
+            let left_term = annotate(*left_expr, annotations);
+            let right_term = annotate(*right_expr, annotations);
+            Term::Application(Box::new(left_term), Box::new(right_term))
+        }
+    }
+}
+
+/// Parse a term, where bound variables can have types
+fn _parse_term(tokens: &[Token]) -> IntersectionParseResult<Term> {
+    let (stripped, annotations) = strip_annotations(tokens)?;
+    let expression = _parse(&stripped).map_err(IntersectionParseError::Expression)?;
+    Ok(annotate(expression, &mut annotations.into_iter()))
+}
+
+/// Parse the context of a judgement: <variable> : <type>, <variable> : <type>, ...
+fn _parse_context(tokens: &[Token]) -> IntersectionParseResult<TypeContext> {
+    let mut context = TypeContext::new();
+    if tokens.is_empty() {
+        return Ok(context);
+    }
+    for binding in tokens.split(|token| *token == Token::Comma) {
+        match binding {
+            [Token::Variable(var), Token::Colon, typ @ ..] => {
+                context.push((var.clone(), _parse_type(typ)?));
+            }
+            _ => return Err(IntersectionParseError::InvalidContext),
+        }
+    }
+    Ok(context)
+}
+
+fn line(tokens: &[Token]) -> IntersectionParseResult<Line> {
+    // split off the context before the turnstile, if there is one
+    let (context, tokens) = match tokens.iter().position(|t| *t == Token::Turnstile) {
+        Some(pos) => {
+            if tokens[pos + 1..].contains(&Token::Turnstile) {
+                return Err(IntersectionParseError::TooManyTurnstiles);
+            }
+            (Some(_parse_context(&tokens[..pos])?), &tokens[pos + 1..])
+        }
+        None => (None, tokens),
+    };
+    match tokens.iter().position(|t| *t == Token::Colon) {
+        Some(colon) => {
+            if tokens[colon + 1..].contains(&Token::Colon) {
+                return Err(IntersectionParseError::TooManyColons);
+            }
+            let term = _parse_term(&tokens[..colon])?;
+            let typ = _parse_type(&tokens[colon + 1..])?;
+            Ok(Line::Judgement(context.unwrap_or_default(), term, typ))
+        }
+        // without a type there is nothing to check, so the context and the types are searched for
+        None if context.is_some() || tokens.contains(&Token::Hat) => {
+            Err(IntersectionParseError::NoType)
+        }
+        None => _parse(tokens)
+            .map(Line::Search)
+            .map_err(IntersectionParseError::Expression),
+    }
+}
+
+/// Parse the tokens into a line of an intersection type file
+/// If given tokens result in an invalid line, prints an error and exits the program
+///
+/// # Arguments
+/// * `tokens` - The tokens to parse
+/// * `idx` - The index of the line the tokens are on (for error printing)
+///
+/// # Returns
+/// The parsed judgement, or the expression to search a typing for
+///
+/// # Error
+/// "Invalid typing [{err_code}] caught during parsing on line {idx}!"
+pub(crate) fn parse_line(tokens: &[Token], idx: usize) -> Line {
+    let line = line(tokens);
+    dbg!(&line);
+    match line {
+        Err(err_code) => {
+            eprintln!(
+                "Invalid typing [{}] caught during parsing on line {}!",
+                err_code,
+                idx + 1
+            );
+            std::process::exit(1);
+        }
+        Ok(line) => line,
+    }
+}
+
+/// Print the type with as few parentheses as the parser allows. \
+/// The arrow (`prec` 0) binds weaker than ∧ (`prec` 1), and variables are atoms (`prec` 2)
+fn type_prec(typ: &Type, prec: u8) -> String {
+    let (own_prec, text) = match typ {
+        Type::Variable(name) => (2, name.clone()),
+        Type::Arrow(domain, codomain) => (
+            0,
+            format!("{} -> {}", type_prec(domain, 1), type_prec(codomain, 0)),
+        ),
+        Type::Intersection(left_type, right_type) => (
+            1,
+            format!("{} ∧ {}", type_prec(left_type, 2), type_prec(right_type, 1)),
+        ),
+    };
+    if own_prec < prec {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
+        write!(fmt, "{}", type_prec(self, 0))
+    }
+}
+
+/// Print the term with as few parentheses as the parser allows. \
+/// The body of an abstraction extends as far right as possible, so it needs parentheses unless it is `rightmost`
+fn term_string(term: &Term, rightmost: bool) -> String {
+    match term {
+        Term::Variable(name) => name.clone(),
+        Term::Abstraction(var, typ, body) => {
+            // the type of a bound variable is an atom
+            let typ = match typ {
+                Some(typ) => format!("^{}", type_prec(typ, 2)),
+                None => String::new(),
+            };
+            let text = format!("λ{}{}.{}", var, typ, term_string(body, true));
+            if rightmost {
+                text
+            } else {
+                format!("({})", text)
+            }
+        }
+        Term::Application(left_term, right_term) => {
+            let right = match **right_term {
+                Term::Application(_, _) => format!("({})", term_string(right_term, true)),
+                _ => term_string(right_term, rightmost),
+            };
+            format!("{} {}", term_string(left_term, false), right)
+        }
+    }
+}
+
+impl Display for Term {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
+        write!(fmt, "{}", term_string(self, true))
+    }
+}
+
+impl Display for Line {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
+        match self {
+            Line::Judgement(context, term, typ) => {
+                let context = context
+                    .iter()
+                    .map(|(var, typ)| format!("{} : {}", var, typ))
+                    .collect::<Vec<String>>();
+                if !context.is_empty() {
+                    write!(fmt, "{} ⊢ ", context.join(", "))?;
+                }
+                write!(fmt, "{} : {}", term, typ)
+            }
+            Line::Search(expression) => write!(fmt, "{}", expression),
+        }
+    }
+}
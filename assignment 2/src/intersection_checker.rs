@@ -0,0 +1,496 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+// Import handy dbg! macro (shadowing std::dbg! macro)
+use crate::dbg;
+use std::fmt::{Display, Formatter, Result};
+use std::rc::Rc;
+
+use crate::intersection::{Term, Type, TypeContext};
+use crate::parser::Expression;
+
+#[derive(Debug)]
+enum IntersectionTypeError {
+    /// A variable that is not in the context, so its type is unknown
+    UnknownType(String),
+    /// A bound variable without a type, of an abstraction that is applied
+    MissingAnnotation(String),
+    /// A term that is applied, and its type, which has no function type among its components
+    NotAFunction(Box<Term>, Box<Type>),
+    /// The expected type, the type that was found, and the term it was found at
+    MismatchedTypes(Box<Type>, Box<Type>, Box<Term>),
+    /// An abstraction, and the type that is not a function type it was checked against
+    CannotHaveType(Box<Term>, Box<Type>),
+    /// The search took too many steps
+    SearchOutOfBounds,
+}
+
+// every step can be a nested call, so the bound is lower than the reducer's to stay within the stack
+const MAX_STEPS: usize = 2000;
+
+impl Display for IntersectionTypeError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            IntersectionTypeError::UnknownType(var) => {
+                write!(f, "Unknown type of variable: {}", var)
+            }
+            IntersectionTypeError::MissingAnnotation(var) => {
+                write!(
+                    f,
+                    "Missing type of bound variable {}, an abstraction that is applied needs one: λ{}^<type>.<term>",
+                    var, var
+                )
+            }
+            IntersectionTypeError::NotAFunction(term, typ) => {
+                write!(
+                    f,
+                    "Expected a function type, found {} at '{}', which cannot be applied",
+                    typ, term
+                )
+            }
+            IntersectionTypeError::MismatchedTypes(expected, found, term) => {
+                write!(
+                    f,
+                    "Mismatched types, expected {}, found {} at '{}'",
+                    expected, found, term
+                )
+            }
+            IntersectionTypeError::CannotHaveType(term, typ) => {
+                write!(
+                    f,
+                    "Expected a function type, but abstraction '{}' is checked against {}",
+                    term, typ
+                )
+            }
+            IntersectionTypeError::SearchOutOfBounds => {
+                write!(
+                    f,
+                    "No typing found within {} steps (the term is not strongly normalising, or needs more steps)",
+                    MAX_STEPS
+                )
+            }
+        }
+    }
+}
+
+type IntersectionTypeResult<T> = std::result::Result<T, IntersectionTypeError>;
+
+/// The types a term of the given type has: the components of an intersection, or the type itself
+fn components(typ: &Type) -> Vec<&Type> {
+    match typ {
+        Type::Intersection(left_type, right_type) => {
+            let mut left = components(left_type);
+            left.extend(components(right_type));
+            left
+        }
+        typ => vec![typ],
+    }
+}
+
+/// The intersection of the types, without duplicate components. \
+/// There is no empty intersection, so there must be at least one type
+fn intersection(types: &[Type]) -> Type {
+    let mut unique: Vec<&Type> = Vec::new();
+    for component in types.iter().flat_map(components) {
+        if !unique.contains(&component) {
+            unique.push(component);
+        }
+    }
+    let last = unique.pop().unwrap().clone();
+    unique
+        .into_iter()
+        .rev()
+        .fold(last, |right_type, left_type| {
+            Type::Intersection(Box::new(left_type.clone()), Box::new(right_type))
+        })
+}
+
+/// Whether the types are equal, where an intersection is a set of components
+/// (∧ is associative, commutative and idempotent)
+fn equivalent(t1: &Type, t2: &Type) -> bool {
+    match (t1, t2) {
+        (Type::Variable(a), Type::Variable(b)) => a == b,
+        (Type::Arrow(domain1, codomain1), Type::Arrow(domain2, codomain2)) => {
+            equivalent(domain1, domain2) && equivalent(codomain1, codomain2)
+        }
+        (Type::Intersection(_, _), _) | (_, Type::Intersection(_, _)) => {
+            let (c1, c2) = (components(t1), components(t2));
+            c1.iter().all(|a| c2.iter().any(|b| equivalent(a, b)))
+                && c2.iter().all(|b| c1.iter().any(|a| equivalent(a, b)))
+        }
+        _ => false,
+    }
+}
+
+/// Synthesise the types of the term in the context, as the components of its intersection type. \
+/// A variable has the components of its type (∧-elimination),
+/// an abstraction with a typed variable has a function type for every type of its body (→-introduction),
+/// and an application has the codomains of the function types of its left term that its right term checks against (→-elimination)
+fn synthesise(term: &Term, context: &mut TypeContext) -> IntersectionTypeResult<Vec<Type>> {
+    match term {
+        Term::Variable(var) => match context.iter().rev().find(|(name, _)| name == var) {
+            Some((_, typ)) => Ok(components(typ).into_iter().cloned().collect()),
+            None => Err(IntersectionTypeError::UnknownType(var.clone())),
+        },
+        Term::Abstraction(var, Some(domain), body) => {
+            context.push((var.clone(), domain.clone()));
+            let codomains = synthesise(body, context);
+            context.pop();
+            Ok(codomains?
+                .into_iter()
+                .map(|codomain| Type::Arrow(Box::new(domain.clone()), Box::new(codomain)))
+                .collect())
+        }
+        Term::Abstraction(var, None, _) => {
+            Err(IntersectionTypeError::MissingAnnotation(var.clone()))
+        }
+        Term::Application(left_term, right_term) => {
+            let types = synthesise(left_term, context)?;
+            let mut result = Vec::new();
+            let mut error = None;
+            for typ in &types {
+                if let Type::Arrow(domain, codomain) = typ {
+                    match check(right_term, domain, context) {
+                        Ok(()) => result.extend(components(codomain).into_iter().cloned()),
+                        Err(err_code) => {
+                            error.get_or_insert(err_code);
+                        }
+                    }
+                }
+            }
+            if result.is_empty() {
+                return Err(error.unwrap_or(IntersectionTypeError::NotAFunction(
+                    left_term.clone(),
+                    Box::new(intersection(&types)),
+                )));
+            }
+            Ok(result)
+        }
+    }
+}
+
+/// Check that the term has the expected type in the context. \
+/// A term has an intersection if it has both of its types (∧-introduction),
+/// and an abstraction has a function type if its body has the codomain (→-introduction),
+/// all other terms need to synthesise the expected type
+fn check(term: &Term, expected: &Type, context: &mut TypeContext) -> IntersectionTypeResult<()> {
+    match (term, expected) {
+        (_, Type::Intersection(left_type, right_type)) => {
+            check(term, left_type, context)?;
+            check(term, right_type, context)
+        }
+        (Term::Abstraction(var, typ, body), Type::Arrow(domain, codomain))
+            if typ.as_ref().is_none_or(|typ| equivalent(typ, domain)) =>
+        {
+            context.push((var.clone(), *domain.clone()));
+            let result = check(body, codomain, context);
+            context.pop();
+            result
+        }
+        (Term::Abstraction(_, None, _), _) => Err(IntersectionTypeError::CannotHaveType(
+            Box::new(term.clone()),
+            Box::new(expected.clone()),
+        )),
+        _ => {
+            let types = synthesise(term, context)?;
+            match types.iter().any(|typ| equivalent(typ, expected)) {
+                true => Ok(()),
+                false => Err(IntersectionTypeError::MismatchedTypes(
+                    Box::new(expected.clone()),
+                    Box::new(intersection(&types)),
+                    Box::new(term.clone()),
+                )),
+            }
+        }
+    }
+}
+
+fn _type_check(context: &TypeContext, term: &Term, expected: &Type) -> IntersectionTypeResult<()> {
+    let mut context = context.clone();
+    check(term, expected, &mut context)
+}
+
+/// Typecheck the term of an intersection type judgement, in its context
+/// If the term does not have the expected type, prints an error and exits the program
+///
+/// # Arguments
+/// * `context` - The types of the free variables of the term
+/// * `term` - The term to typecheck
+/// * `expected` - The type of the judgement
+/// * `idx` - The index of the line the term is on (for error printing)
+///
+/// # Error
+/// "Invalid typing [{err_code}] caught during typechecking on line {idx}!"
+pub(crate) fn intersection_type_check(
+    context: &TypeContext,
+    term: &Term,
+    expected: &Type,
+    idx: usize,
+) {
+    if let Err(err_code) = _type_check(context, term, expected) {
+        eprintln!(
+            "Invalid typing [{}] caught during typechecking on line {}!",
+            err_code,
+            idx + 1
+        );
+        std::process::exit(1);
+    }
+}
+
+/// An expression of the search, where every abstraction has a number (in the order of its lambda)
+#[derive(Debug)]
+enum Node {
+    Variable(String),
+    Abstraction(usize, String, Box<Node>),
+    Application(Box<Node>, Box<Node>),
+}
+
+/// Number the abstractions of the expression, starting at `count`
+fn number(expression: &Expression, count: &mut usize) -> Node {
+    match expression {
+        Expression::Variable(var) => Node::Variable(var.clone()),
+        Expression::Abstraction(var, body) => {
+            *count += 1;
+            let id = *count - 1;
+            Node::Abstraction(id, var.clone(), Box::new(number(body, count)))
+        }
+        Expression::Application(left_expr, right_expr) => {
+            let left_node = number(left_expr, count);
+            let right_node = number(right_expr, count);
+            Node::Application(Box::new(left_node), Box::new(right_node))
+        }
+    }
+}
+
+/// The term of the node, where the abstractions have the types of their bound variables that were found
+fn annotated(node: &Node, annotations: &[Vec<Type>]) -> Term {
+    match node {
+        Node::Variable(var) => Term::Variable(var.clone()),
+        Node::Abstraction(id, var, body) => {
+            let typ = match annotations[*id].is_empty() {
+                true => None,
+                false => Some(intersection(&annotations[*id])),
+            };
+            Term::Abstraction(var.clone(), typ, Box::new(annotated(body, annotations)))
+        }
+        Node::Application(left_node, right_node) => Term::Application(
+            Box::new(annotated(left_node, annotations)),
+            Box::new(annotated(right_node, annotations)),
+        ),
+    }
+}
+
+/// An argument that is not typed yet, with the bindings of its free variables
+#[derive(Clone)]
+struct Closure<'a> {
+    node: &'a Node,
+    env: Env<'a>,
+}
+
+/// A bound variable is an instance of the variable of an abstraction, with its own uses. \
+/// It is bound to the argument of the abstraction if it has one, which is typed at every use of the variable
+enum Binding<'a> {
+    Bound(usize),
+    Pending(usize, Closure<'a>),
+}
+
+struct Scope<'a> {
+    name: &'a str,
+    binding: Binding<'a>,
+    parent: Env<'a>,
+}
+
+/// The bound variables, innermost first
+type Env<'a> = Option<Rc<Scope<'a>>>;
+
+fn lookup<'e, 'a>(env: &'e Env<'a>, name: &str) -> Option<&'e Binding<'a>> {
+    let mut scope = env.as_ref();
+    while let Some(current) = scope {
+        if current.name == name {
+            return Some(&current.binding);
+        }
+        scope = current.parent.as_ref();
+    }
+    None
+}
+
+fn push_unique(types: &mut Vec<Type>, typ: Type) {
+    if !types.contains(&typ) {
+        types.push(typ);
+    }
+}
+
+/// The search for a typing follows the reduction of the term to its normal form: an argument is typed again at every
+/// use of the variable it is substituted for, so a variable that is used at different types gets their intersection. \
+/// The search ends if and only if the term is strongly normalising
+/// (an unused argument is typed too, as it could be reduced), so it is bounded by a number of steps.
+struct Search {
+    /// The types every instance of a bound variable is used at
+    uses: Vec<Vec<Type>>,
+    /// The types every free variable is used at, in order of first use
+    free: Vec<(String, Vec<Type>)>,
+    /// The types of the bound variables of the abstractions that are applied, by their number
+    annotations: Vec<Vec<Type>>,
+    /// The number of type variables so far
+    fresh: usize,
+    steps: usize,
+}
+
+impl Search {
+    /// A new type variable: A, B, ..., Z, A1, B1, ...
+    fn fresh(&mut self) -> Type {
+        let letter = (b'A' + (self.fresh % 26) as u8) as char;
+        let name = match self.fresh / 26 {
+            0 => letter.to_string(),
+            n => format!("{}{}", letter, n),
+        };
+        self.fresh += 1;
+        Type::Variable(name)
+    }
+
+    /// The type of the node, as a function of the types of the arguments it is applied to. \
+    /// `head` is whether the node synthesises its type (is applied), so its abstractions need typed variables
+    fn infer<'a>(
+        &mut self,
+        node: &'a Node,
+        env: &Env<'a>,
+        args: &[Closure<'a>],
+        head: bool,
+    ) -> IntersectionTypeResult<Type> {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            return Err(IntersectionTypeError::SearchOutOfBounds);
+        }
+        match node {
+            Node::Application(left_node, right_node) => {
+                let mut all_args = vec![Closure {
+                    node: right_node,
+                    env: env.clone(),
+                }];
+                all_args.extend_from_slice(args);
+                // the type is a function of all arguments, so without the first it is the codomain
+                match self.infer(left_node, env, &all_args, true)? {
+                    Type::Arrow(_, codomain) => Ok(*codomain),
+                    _ => unreachable!("the type of an applied term is a function type"),
+                }
+            }
+            Node::Abstraction(id, var, body) => {
+                let instance = self.uses.len();
+                self.uses.push(Vec::new());
+                let (binding, rest) = match args.split_first() {
+                    Some((arg, rest)) => (Binding::Pending(instance, arg.clone()), rest),
+                    None => (Binding::Bound(instance), args),
+                };
+                let scope = Some(Rc::new(Scope {
+                    name: var,
+                    binding,
+                    parent: env.clone(),
+                }));
+                let codomain = self.infer(body, &scope, rest, head)?;
+                if self.uses[instance].is_empty() {
+                    let typ = match args.first() {
+                        // the argument is not used, but it must be strongly normalising as well
+                        Some(arg) => self.infer(arg.node, &arg.env, &[], false)?,
+                        None => self.fresh(),
+                    };
+                    self.uses[instance].push(typ);
+                }
+                let domain = intersection(&self.uses[instance]);
+                if head {
+                    for component in components(&domain) {
+                        push_unique(&mut self.annotations[*id], component.clone());
+                    }
+                }
+                Ok(Type::Arrow(Box::new(domain), Box::new(codomain)))
+            }
+            Node::Variable(var) => match lookup(env, var) {
+                // the argument is substituted for the variable, so it is typed at this use
+                Some(Binding::Pending(instance, closure)) => {
+                    let (instance, closure) = (*instance, closure.clone());
+                    let typ = self.infer(closure.node, &closure.env, args, false)?;
+                    push_unique(&mut self.uses[instance], typ.clone());
+                    Ok(typ)
+                }
+                binding => {
+                    let instance = match binding {
+                        Some(Binding::Bound(instance)) => Some(*instance),
+                        _ => None,
+                    };
+                    // the arguments of a variable are in the normal form, so they are typed on their own
+                    let mut domains = Vec::new();
+                    for arg in args {
+                        domains.push(self.infer(arg.node, &arg.env, &[], false)?);
+                    }
+                    let codomain = self.fresh();
+                    let typ = domains
+                        .into_iter()
+                        .rev()
+                        .fold(codomain, |codomain, domain| {
+                            Type::Arrow(Box::new(domain), Box::new(codomain))
+                        });
+                    match instance {
+                        Some(instance) => push_unique(&mut self.uses[instance], typ.clone()),
+                        None => match self.free.iter_mut().find(|(name, _)| name == var) {
+                            Some((_, uses)) => push_unique(uses, typ.clone()),
+                            None => self.free.push((var.clone(), vec![typ.clone()])),
+                        },
+                    }
+                    Ok(typ)
+                }
+            },
+        }
+    }
+}
+
+/// Returns the context, the term with the types of the variables of its applied abstractions, and its type
+fn _search(expression: &Expression) -> IntersectionTypeResult<(TypeContext, Term, Type)> {
+    let mut count = 0;
+    let node = number(expression, &mut count);
+    let mut search = Search {
+        uses: Vec::new(),
+        free: Vec::new(),
+        annotations: vec![Vec::new(); count],
+        fresh: 0,
+        steps: 0,
+    };
+    let typ = search.infer(&node, &None, &[], false)?;
+    dbg!(search.steps);
+    let context: TypeContext = search
+        .free
+        .iter()
+        .map(|(var, uses)| (var.clone(), intersection(uses)))
+        .collect();
+    let term = annotated(&node, &search.annotations);
+    // the typing that was found must be a valid derivation
+    _type_check(&context, &term, &typ)?;
+    Ok((context, term, typ))
+}
+
+/// Search a typing of the expression, with a bounded number of steps (only strongly normalising terms have one)
+/// If no typing is found, prints an error and exits the program
+///
+/// # Arguments
+/// * `expression` - The expression to search a typing for
+/// * `idx` - The index of the line the expression is on (for error printing)
+///
+/// # Returns
+/// The types of the free variables, the term with the types of its applied abstractions, and its type
+///
+/// # Error
+/// "Invalid typing [{err_code}] caught during searching on line {idx}!"
+pub(crate) fn intersection_search(
+    expression: &Expression,
+    idx: usize,
+) -> (TypeContext, Term, Type) {
+    match _search(expression) {
+        Ok(typing) => typing,
+        Err(err_code) => {
+            eprintln!(
+                "Invalid typing [{}] caught during searching on line {}!",
+                err_code,
+                idx + 1
+            );
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    intersection::{parse_line, Line},
+    intersection_checker::{intersection_search, intersection_type_check},
+    line_reader::{read_all_lines_from_file, read_lines_from_terminal},
+    tokenizer::tokenize,
+};
+
+/// Intersection type mode: type every line in the file (or stdin) with intersection types. \
+/// A line is a judgement `context ⊢ term : type`, where the context is optional and bound variables can have a type
+/// (`λx^(A ∧ B).x`), which is checked, or a term without types, of which a typing is searched for. \
+/// Prints every line with its typing, e.g. `λx.x x` prints `λx.x x : A ∧ (A -> B) -> B`. \
+/// `-t [file]`
+pub(super) fn intersection_mode(args: Vec<String>) {
+    let lines = match args.get(2) {
+        Some(filename) => read_all_lines_from_file(filename),
+        None => read_lines_from_terminal(),
+    };
+
+    let typed: Vec<String> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            match parse_line(&tokens, idx) {
+                Line::Judgement(context, term, typ) => {
+                    intersection_type_check(&context, &term, &typ, idx);
+                    Line::Judgement(context, term, typ).to_string()
+                }
+                Line::Search(expression) => {
+                    let (context, term, typ) = intersection_search(&expression, idx);
+                    Line::Judgement(context, term, typ).to_string()
+                }
+            }
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the typed lines
+    for line in typed {
+        println!("{}", line);
+    }
+}
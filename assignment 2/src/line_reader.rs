@@ -3,17 +3,82 @@
 
 // Import handy dbg! macro (shadowing std::dbg! macro)
 use crate::dbg;
+use crate::tokenizer::{in_block_comment, is_blank, is_incomplete};
 
 use std::{
     fs::File,
     io::{stdin, BufRead, BufReader},
 };
 
-/// Read lines from file
-/// Standard mode: read until EOF
-pub(super) fn read_lines_from_file(filename: &str) -> Vec<String> {
-    // get os args input
+/// Group physical lines into expressions, paired with the index of the line they start on. \
+/// Blank and comment-only lines are skipped, and an unfinished expression
+/// (e.g. unbalanced parentheses) is joined with the lines that follow it
+fn logical_lines(lines: Vec<String>) -> Vec<(usize, String)> {
+    join_lines(lines, is_incomplete)
+}
+
+/// Group physical lines into expressions, joining a line with the next while it is `unfinished`. \
+/// Blank and comment-only lines are skipped
+fn join_lines(lines: Vec<String>, unfinished: fn(&str) -> bool) -> Vec<(usize, String)> {
+    let mut logical = Vec::new();
+    let mut pending = String::new();
+    let mut start = 0;
+
+    for (idx, line) in lines.into_iter().enumerate() {
+        if pending.is_empty() {
+            start = idx;
+        } else {
+            pending.push('\n');
+        }
+        pending.push_str(&line);
+
+        if unfinished(&pending) {
+            continue;
+        }
+        if !is_blank(&pending) {
+            logical.push((start, pending));
+        }
+        pending = String::new();
+    }
+    // the input ended while the expression was unfinished, the tokenizer or parser reports it
+    if !pending.is_empty() {
+        logical.push((start, pending));
+    }
+    logical
+}
+
+/// The physical lines, paired with their index, without the blank and comment-only lines. \
+/// Unlike logical_lines, only a block comment that continues on the next line joins them,
+/// every other line is an expression of its own
+fn batch_lines(lines: Vec<String>) -> Vec<(usize, String)> {
+    let lines = join_lines(lines, in_block_comment);
+    dbg!(&lines);
+    lines
+}
+
+/// Read every line of the file, one expression per line (see batch_lines). \
+/// Returns the error if the file cannot be read, instead of exiting. \
+/// Only used for batch mode, where the other files are still processed
+pub(super) fn batch_read_lines_from_file(filename: &str) -> std::io::Result<Vec<(usize, String)>> {
+    let reader = BufReader::new(File::open(filename)?);
+    let lines = reader.lines().collect::<std::io::Result<Vec<String>>>()?;
+    Ok(batch_lines(lines))
+}
 
+/// Read every line from the terminal until EOF, one expression per line (see batch_lines). \
+/// Returns the error if stdin cannot be read, instead of exiting. \
+/// Only used for batch mode
+pub(super) fn batch_read_lines_from_terminal() -> std::io::Result<Vec<(usize, String)>> {
+    let lines = stdin()
+        .lock()
+        .lines()
+        .collect::<std::io::Result<Vec<String>>>()?;
+    Ok(batch_lines(lines))
+}
+
+/// Read all lines from file, until EOF. \
+/// Used by the modes that take more than one expression (e.g. typing mode)
+pub(super) fn read_all_lines_from_file(filename: &str) -> Vec<(usize, String)> {
     let mut lines = Vec::new();
     let file = match File::open(filename) {
         Ok(file) => file,
@@ -25,26 +90,35 @@ pub(super) fn read_lines_from_file(filename: &str) -> Vec<String> {
 
     let reader = BufReader::new(file);
 
-    const MAX_LINES: usize = 1;
-
-    for (i, line) in reader.lines().enumerate() {
-        if (i + 1) > MAX_LINES {
-            eprintln!(
-                "File too large, max lines (assignment spec!) is {}!",
-                MAX_LINES
-            );
-            std::process::exit(1);
-        }
+    for line in reader.lines() {
         lines.push(line.expect("Could not read line"));
     }
-
+    let lines = logical_lines(lines);
     dbg!(&lines);
     lines
 }
 
+/// Read lines from file
+/// Standard mode: read until EOF
+pub(super) fn read_lines_from_file(filename: &str) -> Vec<(usize, String)> {
+    // get os args input
+    let lines = read_all_lines_from_file(filename);
+
+    const MAX_LINES: usize = 1;
+
+    if lines.len() > MAX_LINES {
+        eprintln!(
+            "File too large, max lines (assignment spec!) is {}!",
+            MAX_LINES
+        );
+        std::process::exit(1);
+    }
+    lines
+}
+
 /// Read lines from terminal
 /// Standard mode: read until EOF
-pub(super) fn read_lines_from_terminal() -> Vec<String> {
+pub(super) fn read_lines_from_terminal() -> Vec<(usize, String)> {
     // vec for storing lines
     let mut lines = Vec::new();
 
@@ -61,7 +135,7 @@ pub(super) fn read_lines_from_terminal() -> Vec<String> {
             }
         }
     }
-    lines
+    logical_lines(lines)
 }
 
 /// Read line from terminal
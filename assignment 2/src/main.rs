@@ -19,13 +19,30 @@ use bench::bench;
 mod manual_mode;
 use manual_mode::manual_mode;
 
+mod printer;
+
+mod format_mode;
+use format_mode::format_mode;
+
+mod intersection;
+mod intersection_checker;
+mod intersection_mode;
+use intersection_mode::intersection_mode;
+
+mod batch_mode;
+use batch_mode::batch_mode;
+
+mod dump;
+mod dump_mode;
+use dump_mode::dump_mode;
+
 #[allow(dead_code, unused)]
 mod reducer;
 
 #[allow(dead_code, unused)]
 fn main() {
     // if args provided
-    let lines: Vec<String>;
+    let lines: Vec<(usize, String)>;
     if std::env::args().len() > 1 {
         // get args
         let args = std::env::args().collect::<Vec<String>>();
@@ -39,6 +56,22 @@ fn main() {
                 bench(args);
                 return;
             }
+            "-f" => {
+                format_mode(args);
+                return;
+            }
+            "-t" => {
+                intersection_mode(args);
+                return;
+            }
+            "-l" => {
+                batch_mode(args);
+                return;
+            }
+            arg if arg.starts_with("--dump-ast") => {
+                dump_mode(args);
+                return;
+            }
             arg => {
                 lines = read_lines_from_file(arg);
             }
@@ -59,7 +92,6 @@ fn main() {
 
     let expressions: Vec<Expression> = lines
         .into_iter()
-        .enumerate()
         .map(|(idx, line)| {
             let tokens = tokenize(&line, idx);
             let expression = parse(&tokens, idx);
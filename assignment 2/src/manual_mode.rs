@@ -4,8 +4,10 @@
 use std::io::{self, Write};
 
 use crate::{
-    line_reader::read_line_from_terminal, parser::manual_parse, reducer::manual_reduce,
-    tokenizer::manual_tokenize,
+    line_reader::read_line_from_terminal,
+    parser::manual_parse,
+    reducer::manual_reduce,
+    tokenizer::{is_blank, manual_tokenize},
 };
 
 pub(super) fn manual_mode() {
@@ -15,6 +17,10 @@ pub(super) fn manual_mode() {
         print!("Expression: ");
         io::stdout().flush().unwrap();
         let input = read_line_from_terminal();
+        // nothing to parse on blank or comment-only input
+        if is_blank(&input) {
+            continue;
+        }
         let tokens = manual_tokenize(&input);
         if tokens.is_none() {
             continue;
@@ -3,6 +3,7 @@
 
 // Import handy dbg! macro (shadowing std::dbg! macro)
 use crate::dbg;
+use crate::printer::{pretty, PrintConfig};
 use crate::tokenizer::Token;
 
 use std::fmt::{Display, Formatter, Result};
@@ -25,6 +26,7 @@ pub(super) enum ParseError {
     UnexpectedRParen,
     UnclosedLParen,
     NoAbstractionBody,
+    UnexpectedTypeSyntax,
 }
 
 impl Display for ParseError {
@@ -35,13 +37,16 @@ impl Display for ParseError {
             ParseError::UnexpectedRParen => write!(f, "Unexpected right parenthesis"),
             ParseError::UnclosedLParen => write!(f, "Unclosed left parenthesis"),
             ParseError::NoAbstractionBody => write!(f, "Missing abstraction body"),
+            ParseError::UnexpectedTypeSyntax => {
+                write!(f, "Types are only allowed in typing mode (-t)")
+            }
         }
     }
 }
 
 pub(super) type ParseResult<T> = std::result::Result<T, ParseError>;
 
-fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
+pub(super) fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
     let mut idx = 0;
     let mut result = Vec::new();
 
@@ -69,15 +74,15 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
                                     break;
                                 }
                             }
-                            Token::Variable(_) => {
-                                if paren_count == 0 {
-                                    break;
-                                }
-                            }
+                            Token::Variable(_) if paren_count == 0 => break,
                             _ => {}
                         }
                         end_idx += 1;
                     }
+                    // the body never ended, e.g. an unclosed parenthesis or type syntax after the variable
+                    if end_idx == tokens.len() {
+                        return Err(ParseError::NoAbstractionBody);
+                    }
                 }
 
                 // recursively parse the body of the abstraction
@@ -124,6 +129,13 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
                 result.push(_parse(&tokens[idx + 1..])?);
                 idx = tokens.len();
             }
+            // types are stripped from the tokens before parsing in typing mode (see intersection.rs)
+            Token::Hat
+            | Token::Colon
+            | Token::Arrow
+            | Token::Intersection
+            | Token::Turnstile
+            | Token::Comma => return Err(ParseError::UnexpectedTypeSyntax),
         }
         idx += 1;
     }
@@ -146,64 +158,12 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
     }
 }
 
-/// Display the expression in the normal format
+/// Display the expression in the normal format,
+/// with as few parentheses as possible (see printer.rs)
 /// (Used by e.g. .to_string() and .print() functions)
 impl Display for Expression {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
-        match self {
-            /*
-            If Lambda -> print λ{name}.{expr}
-            Lambda(
-                "x",
-                Application ([
-                    Variable("a"),
-                    Variable("b")
-                ])
-            )
-
-            Becomes
-
-            λx.a b
-            */
-            Expression::Abstraction(name, expr) => {
-                write!(fmt, "λ{name}.{expr}")
-            }
-            /*
-            If Variable -> print {name}
-            Variable("a")
-
-            Becomes
-
-            a
-             */
-            Expression::Variable(name) => write!(fmt, "{name}"),
-            /*
-            If Application
-                If lexpr = abs | app -> "({left_expr})"
-                Else -> "{left_expr}"
-                + " "
-                + If rexpr = app | abs -> "({right_expr})"
-                Else -> "{right_expr}"
-             */
-            Expression::Application(left_expr, right_expr) => {
-                // Left
-                if let Expression::Variable(_) = left_expr.as_ref() {
-                    write!(fmt, "{left_expr}")
-                } else {
-                    write!(fmt, "({left_expr})")
-                }?;
-                // Separator
-                write!(fmt, " ")?;
-                // Right
-                match right_expr.as_ref() {
-                    Expression::Application(_, _) | Expression::Abstraction(_, _) => {
-                        write!(fmt, "({right_expr})")
-                    }
-
-                    r_expr => write!(fmt, "{r_expr}"),
-                }
-            }
-        }
+        write!(fmt, "{}", pretty(self, &PrintConfig::default()))
     }
 }
 
@@ -175,6 +175,17 @@ pub(super) fn reduce(expr: Expression, idx: usize) -> Expression {
     }
 }
 
+/// Reduce the expression \
+/// If there is an error, returns it \
+/// Only used for batch mode, where we want to keep reducing even if there is an error
+pub(super) fn batch_reduce(expr: Expression) -> ReduceResult {
+    CUSTOM_VARNAME_COUNTER.store(1, std::sync::atomic::Ordering::SeqCst);
+    REDUCE_COUNTER.store(1, std::sync::atomic::Ordering::SeqCst);
+    let reduction = _reduce(expr);
+    dbg!(&reduction);
+    reduction
+}
+
 /// Reduce the expression \
 /// Only used for benchmarking \
 /// Unwraps the result, so panics if there is an error, for ultimate speed
@@ -1,35 +1,48 @@
 // L.A. (Luca) Verheul - S3704041
 // Wed 13 Dec 2023
 
-use crate::parser::bench_parse;
-use crate::tokenizer::bench_tokenize;
-use crate::type_checker::bench_type_check;
+use crate::parser::{bench_parse, parse};
+use crate::tokenizer::{bench_tokenize, tokenize};
+use crate::type_checker::{bench_type_check, type_check};
+use crate::utils::mode_usage_error;
 
 pub(super) fn bench(args: Vec<String>) {
-    let expression = args.get(2).expect("No expression given!").clone();
-    let times = args
-        .get(3)
-        .expect("No iterations given!")
-        .parse::<usize>()
-        .unwrap();
+    if matches!(args.get(2).map(String::as_str), Some("-h" | "--help")) {
+        println!("Usage: lc -b <EXPR> <N>\n\nTime tokenizing, parsing and typechecking the judgement EXPR N times");
+        return;
+    }
+    let (Some(expression), Some(times)) = (args.get(2), args.get(3)) else {
+        mode_usage_error("-b expects an expression and a number of iterations", "-b");
+    };
+    let Ok(times) = times.parse::<usize>() else {
+        mode_usage_error(&format!("'{}' is not a number of iterations", times), "-b");
+    };
+    bench_expression(expression, times);
+}
+
+/// Time tokenizing, parsing and typechecking the judgement `times` times (after warming up)
+pub(super) fn bench_expression(expression: &str, times: usize) {
+    // the timed functions unwrap, so check the judgement once first,
+    // exiting with the exit code of the phase it fails in
+    type_check(&parse(&tokenize(expression, 0), 0), 0);
 
     // <Test tokenize>
     //
     // <warmup>
     println!("Warming up...");
     for _ in 0..1000000 {
-        bench_tokenize(&expression);
+        bench_tokenize(expression);
     }
     println!("Done warming up.");
     // <test>
     let now1 = std::time::Instant::now();
     for _ in 0..times {
-        bench_tokenize(&expression);
+        bench_tokenize(expression);
     }
     let elapsed1 = now1.elapsed();
     // </Test tokenize>
 
-    let tokens = bench_tokenize(&expression);
+    let tokens = bench_tokenize(expression);
 
     // <Test parse>
     //
@@ -69,14 +82,14 @@ pub(super) fn bench(args: Vec<String>) {
     // <warmup>
     println!("Warming up...");
     for _ in 0..1000000 {
-        bench_tokenize(&expression);
+        bench_tokenize(expression);
         bench_type_check(&bench_parse(&tokens));
     }
     println!("Done warming up.");
     // <test>
     let now4 = std::time::Instant::now();
     for _ in 0..times {
-        bench_tokenize(&expression);
+        bench_tokenize(expression);
         bench_type_check(&bench_parse(&tokens));
     }
     let elapsed4 = now4.elapsed();
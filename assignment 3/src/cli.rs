@@ -0,0 +1,555 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    bench::bench_expression,
+    dump::DumpFormat,
+    dump_mode::dump_lines,
+    eval_mode::evaluate_lines,
+    format_mode::format_lines,
+    json::{context_json, expression_json, token_json, type_json, Diagnostic, Json, Report},
+    line_reader::{
+        json_read_lines_from_file, json_read_lines_from_terminal, read_lines_from_file,
+        read_lines_from_string, read_lines_from_terminal,
+    },
+    manual_mode::manual_mode,
+    parser::{
+        is_judgement, json_parse, json_parse_open_expression, parse, parse_open_expression,
+        Judgement, Type,
+    },
+    printer::{pretty_open_expression, PrintConfig},
+    reducer::{erase, json_reduce},
+    synth_mode::synthesise_lines,
+    tokenizer::{json_tokenize, tokenize},
+    type_checker::{json_synthesise_type, json_type_check},
+    utils::{exit, Failure},
+};
+
+/// The subcommands of `lc`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Subcommand {
+    Parse,
+    Reduce,
+    Typecheck,
+    Repl,
+    Bench,
+    Fmt,
+}
+
+impl Subcommand {
+    fn from_name(name: &str) -> Option<Subcommand> {
+        match name {
+            "parse" => Some(Subcommand::Parse),
+            "reduce" => Some(Subcommand::Reduce),
+            "typecheck" => Some(Subcommand::Typecheck),
+            "repl" => Some(Subcommand::Repl),
+            "bench" => Some(Subcommand::Bench),
+            "fmt" => Some(Subcommand::Fmt),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Subcommand::Parse => "parse",
+            Subcommand::Reduce => "reduce",
+            Subcommand::Typecheck => "typecheck",
+            Subcommand::Repl => "repl",
+            Subcommand::Bench => "bench",
+            Subcommand::Fmt => "fmt",
+        }
+    }
+
+    /// The usage line and the options of the subcommand, for `lc <subcommand> --help`
+    fn help(&self) -> &'static str {
+        match self {
+            Subcommand::Parse => {
+                "Usage: lc parse [OPTIONS] [FILE]...
+
+Parse every judgement (or expression) and print it back, checking that the printed form parses to the same thing.
+
+Options:
+  -e, --expr <EXPR>        Read the input from EXPR instead of a file (can be repeated)
+      --format <FORMAT>    Print text (default) or json, one object per line
+      --dump-ast <FORMAT>  Print the tree of the parser instead, as a Graphviz graph (dot) or an S-expression (sexp)
+  -h, --help               Print this help"
+            }
+            Subcommand::Reduce => {
+                "Usage: lc reduce [OPTIONS] [FILE]...
+
+Typecheck every judgement (or annotated expression) and print the normal form of its expression.
+
+Options:
+  -e, --expr <EXPR>      Read the input from EXPR instead of a file (can be repeated)
+      --erase            Erase the types from the normal form
+      --format <FORMAT>  Print text (default) or json, one object per line
+  -h, --help             Print this help"
+            }
+            Subcommand::Typecheck => {
+                "Usage: lc typecheck [OPTIONS] [FILE]...
+
+Typecheck every judgement, or synthesise the type of every annotated expression, and print the judgement.
+
+Options:
+  -e, --expr <EXPR>      Read the input from EXPR instead of a file (can be repeated)
+      --format <FORMAT>  Print text (default) or json, one object per line
+  -h, --help             Print this help"
+            }
+            Subcommand::Repl => {
+                "Usage: lc repl
+
+Read judgements from the terminal and typecheck them as they are entered (`:prove <type>` starts a proof).
+
+Options:
+  -h, --help  Print this help"
+            }
+            Subcommand::Bench => {
+                "Usage: lc bench <EXPR> <N>
+
+Time tokenizing, parsing and typechecking the judgement EXPR N times.
+
+Options:
+  -e, --expr <EXPR>  The judgement to benchmark, instead of the first argument
+  -h, --help         Print this help"
+            }
+            Subcommand::Fmt => {
+                "Usage: lc fmt [OPTIONS] [FILE]...
+
+Pretty-print every judgement.
+
+Options:
+  -e, --expr <EXPR>    Read the input from EXPR instead of a file (can be repeated)
+  -a, --ascii          Print \\ and /\\ instead of λ and Λ
+  -s, --space          Separate the bound variable and the body with a space instead of a dot
+  -w, --width <WIDTH>  Break lines that are longer than WIDTH
+  -h, --help           Print this help"
+            }
+        }
+    }
+}
+
+const HELP: &str = "Usage: lc [--left-assoc-arrows] <COMMAND> [OPTIONS] [FILE]...
+
+The typed lambda calculus of assignment 3.
+
+Commands:
+  parse      Parse every line and print it back
+  reduce     Typecheck every line and print its normal form
+  typecheck  Typecheck every line and print its judgement
+  repl       Typecheck lines as they are entered
+  bench      Time tokenizing, parsing and typechecking a judgement
+  fmt        Pretty-print every line
+
+The input is read from the FILEs, from `-e EXPR`, or from stdin if neither is given (or the FILE is `-`).
+`lc -e EXPR` is short for `lc typecheck -e EXPR`.
+
+Options:
+      --left-assoc-arrows  Parse A -> B -> C as (A -> B) -> C
+  -h, --help               Print this help (or that of the command)
+  -V, --version            Print the version
+
+Exit codes:
+  0  Success
+  1  The judgement is not derivable (or the expression has no type)
+  2  The reduction did not reach a normal form
+  3  The input could not be tokenized
+  4  The input could not be parsed
+  5  Invalid command line arguments
+  6  The input could not be read
+
+See `lc <COMMAND> --help` for the options of a command.";
+
+/// Where the lines of the input are read from
+#[derive(Debug)]
+enum Source {
+    Stdin,
+    File(String),
+    /// The expression given with the n-th `-e` (counted from 1)
+    Expression(usize, String),
+}
+
+impl Source {
+    fn lines(&self) -> Vec<(usize, String)> {
+        match self {
+            Source::Stdin => read_lines_from_terminal(),
+            Source::File(filename) => read_lines_from_file(filename),
+            Source::Expression(_, expression) => read_lines_from_string(expression),
+        }
+    }
+
+    /// The lines of the source, or the error if it cannot be read (see `json_lines`)
+    fn json_lines(&self) -> std::io::Result<Vec<(usize, String)>> {
+        match self {
+            Source::Stdin => json_read_lines_from_terminal(),
+            Source::File(filename) => json_read_lines_from_file(filename),
+            Source::Expression(_, expression) => Ok(read_lines_from_string(expression)),
+        }
+    }
+
+    /// The name the JSON output reports the source by: the file path, `<stdin>`, or `-e#n`
+    fn name(&self) -> String {
+        match self {
+            Source::Stdin => "<stdin>".to_string(),
+            Source::File(filename) => filename.clone(),
+            Source::Expression(n, _) => format!("-e#{}", n),
+        }
+    }
+}
+
+/// The parsed command line of a subcommand
+#[derive(Debug)]
+struct Options {
+    subcommand: Subcommand,
+    sources: Vec<Source>,
+    /// The arguments that are not options (the expression and iterations of bench)
+    arguments: Vec<String>,
+    erase: bool,
+    /// Print one JSON object per line (`--format json`) instead of text
+    json: bool,
+    /// Print the tree of the parser instead (`--dump-ast`)
+    dump: Option<DumpFormat>,
+    config: PrintConfig,
+}
+
+/// Print the error with a hint to the help, and exit with the usage exit code
+fn usage_error(message: &str, subcommand: Option<Subcommand>) -> ! {
+    eprintln!("lc: {}", message);
+    match subcommand {
+        Some(subcommand) => eprintln!(
+            "Try 'lc {} --help' for more information.",
+            subcommand.name()
+        ),
+        None => eprintln!("Try 'lc --help' for more information."),
+    }
+    exit(Failure::Usage);
+}
+
+/// Parse the arguments after the subcommand. \
+/// Long options can also be given as `--option=value`, and `--` ends the options
+fn parse_options(subcommand: Subcommand, args: &[String]) -> Options {
+    let mut options = Options {
+        subcommand,
+        sources: Vec::new(),
+        arguments: Vec::new(),
+        erase: false,
+        json: false,
+        dump: None,
+        config: PrintConfig::default(),
+    };
+    let mut args = args.iter();
+    let mut only_arguments = false;
+    while let Some(arg) = args.next() {
+        if only_arguments || arg == "-" || !arg.starts_with('-') {
+            match (subcommand, arg.as_str()) {
+                (Subcommand::Bench, _) => options.arguments.push(arg.clone()),
+                (Subcommand::Repl, _) => {
+                    usage_error(&format!("unexpected argument '{}'", arg), Some(subcommand))
+                }
+                (_, "-") => options.sources.push(Source::Stdin),
+                _ => options.sources.push(Source::File(arg.clone())),
+            }
+            continue;
+        }
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        // the value of an option, inline or as the next argument
+        let mut value = || match inline_value.clone().or_else(|| args.next().cloned()) {
+            Some(value) => value,
+            None => usage_error(&format!("{} expects a value", flag), Some(subcommand)),
+        };
+        match (subcommand, flag) {
+            (_, "-h" | "--help") => {
+                println!("{}", subcommand.help());
+                std::process::exit(0);
+            }
+            (_, "--") => only_arguments = true,
+            (Subcommand::Repl, _) => {
+                usage_error(&format!("unexpected option '{}'", flag), Some(subcommand))
+            }
+            (Subcommand::Bench, "-e" | "--expr") => options.arguments.insert(0, value()),
+            (_, "-e" | "--expr") => {
+                let n = options
+                    .sources
+                    .iter()
+                    .filter(|source| matches!(source, Source::Expression(_, _)))
+                    .count();
+                options.sources.push(Source::Expression(n + 1, value()))
+            }
+            (Subcommand::Reduce, "--erase") => options.erase = true,
+            (Subcommand::Parse | Subcommand::Reduce | Subcommand::Typecheck, "--format") => {
+                match value().as_str() {
+                    "text" => options.json = false,
+                    "json" => options.json = true,
+                    format => usage_error(
+                        &format!("unknown format '{}', expected text or json", format),
+                        Some(subcommand),
+                    ),
+                }
+            }
+            (Subcommand::Parse, "--dump-ast") => match DumpFormat::from_name(&value()) {
+                Some(format) => options.dump = Some(format),
+                None => usage_error("--dump-ast expects dot or sexp", Some(subcommand)),
+            },
+            (Subcommand::Fmt, "-a" | "--ascii") => options.config.unicode = false,
+            (Subcommand::Fmt, "-s" | "--space") => options.config.dot = false,
+            (Subcommand::Fmt, "-w" | "--width") => match value().parse() {
+                Ok(width) => options.config.width = Some(width),
+                Err(_) => usage_error(&format!("{} expects a number", flag), Some(subcommand)),
+            },
+            _ => usage_error(&format!("unexpected option '{}'", flag), Some(subcommand)),
+        }
+    }
+    if options.sources.is_empty() {
+        options.sources.push(Source::Stdin);
+    }
+    options
+}
+
+/// Parse every line and print it back, like normal mode without typechecking
+fn parse_lines(lines: Vec<(usize, String)>) {
+    let parsed: Vec<String> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            let (printed, reprinted) = if is_judgement(&tokens) {
+                let printed = parse(&tokens, idx).to_string();
+                let reprinted = parse(&tokenize(&printed, idx), idx).to_string();
+                (printed, reprinted)
+            } else {
+                let config = PrintConfig::default();
+                let (context, expression) = parse_open_expression(&tokens, idx);
+                let printed = pretty_open_expression(&context, &expression, &config);
+                let (context, expression) = parse_open_expression(&tokenize(&printed, idx), idx);
+                let reprinted = pretty_open_expression(&context, &expression, &config);
+                (printed, reprinted)
+            };
+            if printed != reprinted {
+                eprintln!(
+                    "Invalid expression (on second parse) '{}' is not equal to '{}' on line {}!",
+                    reprinted,
+                    printed,
+                    idx + 1
+                );
+                exit(Failure::Parse);
+            }
+            printed
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the lines
+    for line in parsed {
+        println!("{}", line);
+    }
+}
+
+/// Fill in the report of the line, as far as the subcommand goes (see `json_lines`). \
+/// Returns the error of the first phase that fails
+fn report_line(
+    report: &mut Report,
+    subcommand: Subcommand,
+    erased: bool,
+) -> std::result::Result<(), Diagnostic> {
+    let idx = report.line - 1;
+    let (tokens, positions) = json_tokenize(&report.input, idx)?;
+    report.tokens = Some(Json::Array(tokens.iter().map(token_json).collect()));
+
+    let (judgement, typ) = if is_judgement(&tokens) {
+        let judgement = json_parse(&tokens, &positions, idx)?;
+        let Judgement::Judgement(_, _, typ) = &judgement;
+        let typ = (**typ).clone();
+        (judgement, Some(typ))
+    } else {
+        let (context, expression) = json_parse_open_expression(&tokens, &positions, idx)?;
+        (
+            Judgement::Judgement(context, Box::new(expression), Box::new(Type::Unit)),
+            None,
+        )
+    };
+    let Judgement::Judgement(context, expression, _) = &judgement;
+    report.context = Some(context_json(context));
+    report.expression = Some(expression_json(expression));
+    report.typ = typ.as_ref().map(type_json);
+
+    if subcommand == Subcommand::Parse {
+        let config = PrintConfig::default();
+        let printed = match typ {
+            Some(_) => judgement.to_string(),
+            None => pretty_open_expression(context, expression, &config),
+        };
+        // the printed form has to parse to the same thing, like `lc parse`
+        let (retokens, repositions) = json_tokenize(&printed, idx)?;
+        let reprinted = match typ {
+            Some(_) => json_parse(&retokens, &repositions, idx)?.to_string(),
+            None => {
+                let (context, expression) =
+                    json_parse_open_expression(&retokens, &repositions, idx)?;
+                pretty_open_expression(&context, &expression, &config)
+            }
+        };
+        if printed != reprinted {
+            return Err(Diagnostic {
+                failure: Failure::Parse,
+                code: "reparse_mismatch",
+                message: format!("'{}' is not equal to '{}'", reprinted, printed),
+                line: idx + 1,
+                column: None,
+                underline: None,
+            });
+        }
+        report.output = Some(printed);
+        return Ok(());
+    }
+
+    // only well-typed expressions are reduced, so they do not get stuck
+    let typ = match typ {
+        Some(typ) => {
+            json_type_check(&judgement, idx)?;
+            typ
+        }
+        None => {
+            let typ = json_synthesise_type(expression, context, idx)?;
+            report.typ = Some(type_json(&typ));
+            typ
+        }
+    };
+    if subcommand == Subcommand::Typecheck {
+        let typed = Judgement::Judgement(context.clone(), expression.clone(), Box::new(typ));
+        report.output = Some(typed.to_string());
+        return Ok(());
+    }
+
+    let (normal_form, steps) = json_reduce(*expression.clone(), idx)?;
+    let normal_form = if erased {
+        erase(&normal_form)
+    } else {
+        normal_form
+    };
+    report.normal_form = Some(expression_json(&normal_form));
+    report.steps = Some(steps);
+    report.output = Some(normal_form.to_string());
+    Ok(())
+}
+
+/// Print one JSON object for every line of the source named `source` (see the README for the schema),
+/// continuing past lines that fail. A source that cannot be read is reported as one object with an input error. \
+/// Returns the failure of the first line that failed
+fn json_lines(
+    source: &str,
+    lines: std::io::Result<Vec<(usize, String)>>,
+    subcommand: Subcommand,
+    erased: bool,
+) -> Option<Failure> {
+    let lines = match lines {
+        Ok(lines) => lines,
+        Err(error) => {
+            let report = Report {
+                source: source.to_string(),
+                error: Some(Diagnostic {
+                    failure: Failure::Input,
+                    code: "unreadable_input",
+                    message: format!("Could not read {}: {}", source, error),
+                    line: 0,
+                    column: None,
+                    underline: None,
+                }),
+                ..Report::default()
+            };
+            println!("{}", report.json());
+            return Some(Failure::Input);
+        }
+    };
+    let mut failure = None;
+    for (idx, line) in lines {
+        let mut report = Report {
+            source: source.to_string(),
+            line: idx + 1,
+            input: line,
+            ..Report::default()
+        };
+        if let Err(diagnostic) = report_line(&mut report, subcommand, erased) {
+            failure = failure.or(Some(diagnostic.failure));
+            report.error = Some(diagnostic);
+        }
+        println!("{}", report.json());
+    }
+    failure
+}
+
+/// The `lc` command line: `lc <COMMAND> [OPTIONS] [FILE]...`, see `lc --help`. \
+/// Every failure exits with its own exit code
+pub(super) fn cli(args: Vec<String>) {
+    let subcommand = match args.get(1).map(String::as_str) {
+        None | Some("-h" | "--help") => {
+            println!("{}", HELP);
+            return;
+        }
+        Some("-V" | "--version") => {
+            println!("lc {}", env!("CARGO_PKG_VERSION"));
+            return;
+        }
+        // `lc -e EXPR` is short for `lc typecheck -e EXPR`, like normal mode for a file
+        Some("-e" | "--expr") => {
+            let options = parse_options(Subcommand::Typecheck, &args[1..]);
+            return run(options);
+        }
+        // the word is not a file either, see main.rs
+        Some(name) => match Subcommand::from_name(name) {
+            Some(subcommand) => subcommand,
+            None => usage_error(&format!("unknown command or file '{}'", name), None),
+        },
+    };
+    run(parse_options(subcommand, &args[2..]));
+}
+
+/// Run the subcommand with its options
+fn run(options: Options) {
+    let subcommand = options.subcommand;
+
+    match options.subcommand {
+        Subcommand::Repl => manual_mode(),
+        Subcommand::Bench => match options.arguments.as_slice() {
+            [expression, times] => match times.parse() {
+                Ok(times) => bench_expression(expression, times),
+                Err(_) => usage_error(
+                    "the number of iterations must be a number",
+                    Some(subcommand),
+                ),
+            },
+            _ => usage_error(
+                "bench expects an expression and a number of iterations",
+                Some(subcommand),
+            ),
+        },
+        // the tree of the parser is dumped instead of the JSON output
+        _ if options.json && options.dump.is_none() => {
+            let mut failure = None;
+            for source in &options.sources {
+                let lines = source.json_lines();
+                failure = failure.or(json_lines(
+                    &source.name(),
+                    lines,
+                    options.subcommand,
+                    options.erase,
+                ));
+            }
+            if let Some(failure) = failure {
+                exit(failure);
+            }
+        }
+        _ => {
+            for source in &options.sources {
+                let lines = source.lines();
+                match options.subcommand {
+                    Subcommand::Parse => match options.dump {
+                        Some(format) => dump_lines(lines, format),
+                        None => parse_lines(lines),
+                    },
+                    Subcommand::Reduce => evaluate_lines(lines, options.erase),
+                    Subcommand::Typecheck => synthesise_lines(lines),
+                    _ => format_lines(lines, &options.config),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,445 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+// Import handy dbg! macro (shadowing std::dbg! macro)
+use crate::dbg;
+use crate::tokenizer::Token;
+use crate::utils::{exit, Failure};
+
+use std::fmt::{Display, Formatter, Result};
+
+/// The sorts of the calculus of constructions: `*` is the type of types, `□` the type of `*`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Sort {
+    /// `*`
+    Star,
+    /// `□`
+    Square,
+}
+
+/// A term of the calculus of constructions, types are terms too
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum Term {
+    /// <Variable>, lowercase or uppercase
+    Variable(String),
+    /// * or □
+    Sort(Sort),
+    /// λ <Variable> : <Term> . <Term>, or Λ for an uppercase variable
+    Abstraction(String, Box<Term>, Box<Term>),
+    /// Π <Variable> : <Term> . <Term>, or <Term> -> <Term> if the variable is not used
+    Pi(String, Box<Term>, Box<Term>),
+    /// <Term> <Term>
+    Application(Box<Term>, Box<Term>),
+}
+
+/// The types of the free variables of a term, later bindings shadow earlier ones
+pub(crate) type TermContext = Vec<(String, Term)>;
+
+/// A line of a calculus of constructions file
+#[derive(Debug)]
+pub(crate) enum Line {
+    /// <Variable> := <Term>, a definition that the following lines can use
+    Definition(String, Term),
+    /// <Context> ⊢ <Term> : <Term>, where the context and the type are optional
+    Judgement(TermContext, Term, Option<Term>),
+}
+
+#[derive(Debug)]
+enum CocParseError {
+    EmptyTerm,
+    UnexpectedRParen,
+    UnclosedLParen,
+    InvalidBinder,
+    NoBinderBody,
+    InvalidContext,
+    TooManyTurnstiles,
+    InvalidDefinition,
+    UnexpectedToken,
+}
+
+impl Display for CocParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            CocParseError::EmptyTerm => write!(f, "Empty term"),
+            CocParseError::UnexpectedRParen => write!(f, "Unexpected right parenthesis"),
+            CocParseError::UnclosedLParen => write!(f, "Unclosed left parenthesis"),
+            CocParseError::InvalidBinder => {
+                write!(
+                    f,
+                    "Invalid binder, should be: λ<variable>:<term>. <term> or Π<variable>:<term>. <term>"
+                )
+            }
+            CocParseError::NoBinderBody => write!(f, "Missing body of binder"),
+            CocParseError::InvalidContext => {
+                write!(
+                    f,
+                    "Invalid context, should be: <variable> : <term>, ... ⊢ <term> : <term>"
+                )
+            }
+            CocParseError::TooManyTurnstiles => write!(f, "Too many turnstiles"),
+            CocParseError::InvalidDefinition => {
+                write!(f, "Invalid definition, should be: <variable> := <term>")
+            }
+            CocParseError::UnexpectedToken => {
+                write!(
+                    f,
+                    "Unexpected token, terms are built from variables, sorts, binders, arrows and applications"
+                )
+            }
+        }
+    }
+}
+
+type CocParseResult<T> = std::result::Result<T, CocParseError>;
+
+/// The index of the first token of a binder (λ, Λ or Π) outside of parentheses, if there is one
+fn binder_start(tokens: &[Token]) -> CocParseResult<Option<usize>> {
+    let mut paren_count = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LParen => paren_count += 1,
+            Token::RParen if paren_count == 0 => return Err(CocParseError::UnexpectedRParen),
+            Token::RParen => paren_count -= 1,
+            Token::Lambda(_) | Token::TypeLambda(_) | Token::Forall if paren_count == 0 => {
+                return Ok(Some(idx));
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+/// The index of the first token outside of parentheses that is equal to the separator,
+/// before the first binder (the body of a binder extends as far right as possible)
+fn find_top_level(tokens: &[Token], separator: &Token) -> CocParseResult<Option<usize>> {
+    let end = binder_start(tokens)?.unwrap_or(tokens.len());
+    let mut paren_count = 0;
+    for (idx, token) in tokens[..end].iter().enumerate() {
+        match token {
+            Token::LParen => paren_count += 1,
+            Token::RParen => paren_count -= 1,
+            token if paren_count == 0 && token == separator => return Ok(Some(idx)),
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+/// Parse a term: a binder, or an application that may be the left side of an arrow
+fn _parse_term(tokens: &[Token]) -> CocParseResult<Term> {
+    match tokens {
+        [] => Err(CocParseError::EmptyTerm),
+        [Token::Lambda(var) | Token::TypeLambda(var), Token::Colon, rest @ ..] => {
+            let (typ, body) = binder(rest)?;
+            Ok(Term::Abstraction(
+                var.clone(),
+                Box::new(typ),
+                Box::new(body),
+            ))
+        }
+        [Token::Forall, Token::LVariable(var) | Token::UVariable(var), Token::Colon, rest @ ..] => {
+            let (typ, body) = binder(rest)?;
+            Ok(Term::Pi(var.clone(), Box::new(typ), Box::new(body)))
+        }
+        [Token::Lambda(_) | Token::TypeLambda(_) | Token::Forall, ..] => {
+            Err(CocParseError::InvalidBinder)
+        }
+        _ => match find_top_level(tokens, &Token::Arrow)? {
+            // A -> B is Π_:A. B, the arrow associates to the right
+            Some(arrow) => Ok(Term::Pi(
+                "_".to_string(),
+                Box::new(_parse_application(&tokens[..arrow])?),
+                Box::new(_parse_term(&tokens[arrow + 1..])?),
+            )),
+            None => _parse_application(tokens),
+        },
+    }
+}
+
+/// The type and the body of a binder, after its colon: <Term> . <Term>. \
+/// The type ends at the first dot, so a binder in it needs parentheses
+fn binder(tokens: &[Token]) -> CocParseResult<(Term, Term)> {
+    let mut paren_count = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LParen => paren_count += 1,
+            Token::RParen => paren_count -= 1,
+            Token::Dot if paren_count == 0 => {
+                if idx + 1 >= tokens.len() {
+                    return Err(CocParseError::NoBinderBody);
+                }
+                return Ok((
+                    _parse_term(&tokens[..idx])?,
+                    _parse_term(&tokens[idx + 1..])?,
+                ));
+            }
+            _ => {}
+        }
+    }
+    Err(CocParseError::InvalidBinder)
+}
+
+/// <Atom> <Atom> ..., associating to the left, where the last atom can be a binder
+fn _parse_application(tokens: &[Token]) -> CocParseResult<Term> {
+    let mut result = Vec::new();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        match &tokens[idx] {
+            Token::LVariable(var) | Token::UVariable(var) => {
+                result.push(Term::Variable(var.clone()));
+            }
+            Token::Star => result.push(Term::Sort(Sort::Star)),
+            Token::Square => result.push(Term::Sort(Sort::Square)),
+            Token::LParen => {
+                let mut paren_count = 1;
+                let mut end_idx = idx + 1;
+                while end_idx < tokens.len() {
+                    match tokens[end_idx] {
+                        Token::LParen => paren_count += 1,
+                        Token::RParen => paren_count -= 1,
+                        _ => {}
+                    }
+                    if paren_count == 0 {
+                        break;
+                    }
+                    end_idx += 1;
+                }
+                if paren_count != 0 {
+                    return Err(CocParseError::UnclosedLParen);
+                }
+                result.push(_parse_term(&tokens[idx + 1..end_idx])?);
+                idx = end_idx;
+            }
+            Token::RParen => return Err(CocParseError::UnexpectedRParen),
+            // like after a dot, a binder extends as far right as possible
+            Token::Lambda(_) | Token::TypeLambda(_) | Token::Forall => {
+                result.push(_parse_term(&tokens[idx..])?);
+                idx = tokens.len();
+            }
+            _ => return Err(CocParseError::UnexpectedToken),
+        }
+        idx += 1;
+    }
+    match result.into_iter().reduce(|left_term, right_term| {
+        Term::Application(Box::new(left_term), Box::new(right_term))
+    }) {
+        Some(term) => Ok(term),
+        None => Err(CocParseError::EmptyTerm),
+    }
+}
+
+/// Parse the context of a judgement: <variable> : <term>, <variable> : <term>, ...
+fn _parse_context(tokens: &[Token]) -> CocParseResult<TermContext> {
+    if tokens.is_empty() {
+        return Ok(TermContext::new());
+    }
+    let mut context = TermContext::new();
+    let mut rest = tokens;
+    loop {
+        let (binding, next) = match find_top_level(rest, &Token::Comma)? {
+            Some(comma) => (&rest[..comma], Some(&rest[comma + 1..])),
+            None => (rest, None),
+        };
+        match binding {
+            [Token::LVariable(var) | Token::UVariable(var), Token::Colon, typ @ ..] => {
+                context.push((var.clone(), _parse_term(typ)?));
+            }
+            _ => return Err(CocParseError::InvalidContext),
+        }
+        match next {
+            Some(next) => rest = next,
+            None => return Ok(context),
+        }
+    }
+}
+
+/// The index of the colon between the term and the type of a judgement, if there is one:
+/// the last colon outside of parentheses that is not the colon of a binder
+fn judgement_colon(tokens: &[Token]) -> Option<usize> {
+    let mut paren_count = 0;
+    let mut colon = None;
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LParen => paren_count += 1,
+            Token::RParen => paren_count -= 1,
+            Token::Colon if paren_count == 0 => {
+                let binder = match idx {
+                    0 => false,
+                    1 => matches!(tokens[0], Token::Lambda(_) | Token::TypeLambda(_)),
+                    _ => {
+                        matches!(tokens[idx - 1], Token::Lambda(_) | Token::TypeLambda(_))
+                            || tokens[idx - 2] == Token::Forall
+                    }
+                };
+                if !binder {
+                    colon = Some(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    colon
+}
+
+/// The PCF keywords are only reserved in the typed language, which has PCF syntax.
+/// Here they are variable names, e.g. `succ` for the successor of Church numerals
+fn unreserve_keyword(token: &Token) -> Token {
+    let name = match token {
+        Token::Succ => "succ",
+        Token::Pred => "pred",
+        Token::IsZero => "iszero",
+        Token::True => "true",
+        Token::False => "false",
+        Token::If => "if",
+        Token::Then => "then",
+        Token::Else => "else",
+        Token::Fix => "fix",
+        _ => return token.clone(),
+    };
+    Token::LVariable(name.to_string())
+}
+
+fn line(tokens: &[Token]) -> CocParseResult<Line> {
+    let tokens: Vec<Token> = tokens.iter().map(unreserve_keyword).collect();
+    let tokens = tokens.as_slice();
+    // <Variable> := <Term>
+    if let [Token::LVariable(name) | Token::UVariable(name), Token::Colon, Token::Equals, rest @ ..] =
+        tokens
+    {
+        return Ok(Line::Definition(name.clone(), _parse_term(rest)?));
+    }
+    if tokens.contains(&Token::Equals) {
+        return Err(CocParseError::InvalidDefinition);
+    }
+
+    // split off the context before the turnstile, if there is one
+    let (context, tokens) = match tokens.iter().position(|t| *t == Token::Turnstile) {
+        Some(pos) => {
+            if tokens[pos + 1..].contains(&Token::Turnstile) {
+                return Err(CocParseError::TooManyTurnstiles);
+            }
+            (_parse_context(&tokens[..pos])?, &tokens[pos + 1..])
+        }
+        None => (TermContext::new(), tokens),
+    };
+    match judgement_colon(tokens) {
+        Some(colon) => Ok(Line::Judgement(
+            context,
+            _parse_term(&tokens[..colon])?,
+            Some(_parse_term(&tokens[colon + 1..])?),
+        )),
+        None => Ok(Line::Judgement(context, _parse_term(tokens)?, None)),
+    }
+}
+
+/// Parse the tokens into a line of a calculus of constructions file
+/// If given tokens result in an invalid line, prints an error and exits the program
+///
+/// # Arguments
+/// * `tokens` - The tokens to parse
+/// * `idx` - The index of the line the tokens are on (for error printing)
+///
+/// # Returns
+/// The parsed definition or judgement
+///
+/// # Error
+/// "Invalid term [{err_code}] caught during parsing on line {idx}!"
+pub(crate) fn parse_line(tokens: &[Token], idx: usize) -> Line {
+    let line = line(tokens);
+    dbg!(&line);
+    match line {
+        Err(err_code) => {
+            eprintln!(
+                "Invalid term [{}] caught during parsing on line {}!",
+                err_code,
+                idx + 1
+            );
+            exit(Failure::Parse);
+        }
+        Ok(line) => line,
+    }
+}
+
+/// Whether the variable occurs free in the term
+pub(crate) fn occurs_free(var: &str, term: &Term) -> bool {
+    match term {
+        Term::Variable(name) => name == var,
+        Term::Sort(_) => false,
+        Term::Abstraction(bound, typ, body) | Term::Pi(bound, typ, body) => {
+            occurs_free(var, typ) || (bound != var && occurs_free(var, body))
+        }
+        Term::Application(left_term, right_term) => {
+            occurs_free(var, left_term) || occurs_free(var, right_term)
+        }
+    }
+}
+
+/// Print the term with as few parentheses as the parser allows. \
+/// Binders extend as far right as possible, so they need parentheses unless they are `rightmost`,
+/// application (`prec` 1) binds tighter than the arrow (`prec` 0), and variables and sorts are atoms (`prec` 2)
+fn term_prec(term: &Term, prec: u8, rightmost: bool) -> String {
+    let (own_prec, text) = match term {
+        Term::Variable(name) => (2, name.clone()),
+        Term::Sort(Sort::Star) => (2, "*".to_string()),
+        Term::Sort(Sort::Square) => (2, "□".to_string()),
+        Term::Pi(var, typ, body) if !occurs_free(var, body) => {
+            let left = term_prec(typ, 1, false);
+            let right = term_prec(body, 0, rightmost || prec > 0);
+            (0, format!("{} -> {}", left, right))
+        }
+        Term::Abstraction(var, typ, body) | Term::Pi(var, typ, body) => {
+            // the lexer only allows lowercase variables after a λ
+            let binder = match term {
+                Term::Pi(_, _, _) => "Π",
+                _ if var.starts_with(|c: char| c.is_ascii_uppercase()) => "Λ",
+                _ => "λ",
+            };
+            // the type ends at the dot, so a binder in it needs parentheses
+            let typ = term_prec(typ, 0, false);
+            let body = term_prec(body, 0, true);
+            (0, format!("{}{}:{}. {}", binder, var, typ, body))
+        }
+        Term::Application(left_term, right_term) => {
+            let left = term_prec(left_term, 1, false);
+            let right = term_prec(right_term, 2, false);
+            (1, format!("{} {}", left, right))
+        }
+    };
+    let binder = match term {
+        Term::Abstraction(_, _, _) => true,
+        Term::Pi(var, _, body) => occurs_free(var, body),
+        _ => false,
+    };
+    if own_prec < prec || (binder && !rightmost) {
+        format!("({})", text)
+    } else {
+        text
+    }
+}
+
+impl Display for Term {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
+        write!(fmt, "{}", term_prec(self, 0, true))
+    }
+}
+
+impl Display for Line {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
+        match self {
+            Line::Definition(name, term) => write!(fmt, "{} := {}", name, term),
+            Line::Judgement(context, term, typ) => {
+                let context = context
+                    .iter()
+                    .map(|(var, typ)| format!("{} : {}", var, typ))
+                    .collect::<Vec<String>>();
+                if !context.is_empty() {
+                    write!(fmt, "{} ⊢ ", context.join(", "))?;
+                }
+                match typ {
+                    Some(typ) => write!(fmt, "{} : {}", term, typ),
+                    None => write!(fmt, "{}", term),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+// Import handy dbg! macro (shadowing std::dbg! macro)
+use crate::dbg;
+use crate::reducer::{
+    normalise_with, reset_counters, substitute_binder, unique_varname, ReduceError, Substitute,
+};
+use crate::utils::{exit, Failure};
+use std::fmt::{Display, Formatter, Result};
+
+use crate::coc::{occurs_free, Sort, Term, TermContext};
+
+#[derive(Debug)]
+enum CocTypeError {
+    /// A variable that is not in the context, so its type is unknown
+    UnknownType(String),
+    /// □ is the top sort, so it has no type itself
+    SquareHasNoType,
+    /// A term that is used as a type, and its type, which is not a sort
+    NotAType(Box<Term>, Box<Term>),
+    /// A term that is applied, and its type, which is not a Π-type
+    NotAFunction(Box<Term>, Box<Term>),
+    /// The expected type, the type that was found, and the term it was found at
+    MismatchedTypes(Box<Term>, Box<Term>, Box<Term>),
+    /// Normalising took too many steps
+    Reduce(ReduceError),
+}
+
+impl Display for CocTypeError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            CocTypeError::UnknownType(var) => write!(f, "Unknown type of variable: {}", var),
+            CocTypeError::SquareHasNoType => write!(f, "□ has no type"),
+            CocTypeError::NotAType(term, typ) => {
+                write!(
+                    f,
+                    "Expected a type (of sort * or □), found '{}' of type {}",
+                    term, typ
+                )
+            }
+            CocTypeError::NotAFunction(term, typ) => {
+                write!(
+                    f,
+                    "Expected a Π-type, found {} at '{}', which cannot be applied",
+                    typ, term
+                )
+            }
+            CocTypeError::MismatchedTypes(expected, found, term) => {
+                write!(
+                    f,
+                    "Mismatched types, expected {}, found {} at '{}' (not β-equivalent)",
+                    expected, found, term
+                )
+            }
+            CocTypeError::Reduce(err) => write!(f, "{} while normalising", err),
+        }
+    }
+}
+
+type CocTypeResult<T> = std::result::Result<T, CocTypeError>;
+
+impl Substitute for Term {
+    fn variable(name: String) -> Self {
+        Term::Variable(name)
+    }
+
+    fn is_free(&self, var: &str) -> bool {
+        occurs_free(var, self)
+    }
+
+    fn substitute(self, symbol: &str, subterm: &Self) -> Self {
+        match self {
+            Term::Variable(name) if name == symbol => subterm.clone(),
+            Term::Variable(_) | Term::Sort(_) => self,
+            Term::Application(left_term, right_term) => Term::Application(
+                Box::new(left_term.substitute(symbol, subterm)),
+                Box::new(right_term.substitute(symbol, subterm)),
+            ),
+            Term::Abstraction(var, typ, body) => {
+                let (var, body) = substitute_binder(var, *body, symbol, subterm);
+                Term::Abstraction(
+                    var,
+                    Box::new(typ.substitute(symbol, subterm)),
+                    Box::new(body),
+                )
+            }
+            Term::Pi(var, typ, body) => {
+                let (var, body) = substitute_binder(var, *body, symbol, subterm);
+                Term::Pi(
+                    var,
+                    Box::new(typ.substitute(symbol, subterm)),
+                    Box::new(body),
+                )
+            }
+        }
+    }
+}
+
+/// The term after one β-reduction step, leftmost outermost first, also under binders and in types. \
+/// Returns None if the term is in normal form
+fn step(term: &Term) -> Option<Term> {
+    match term {
+        Term::Variable(_) | Term::Sort(_) => None,
+        Term::Application(left_term, right_term) => match left_term.as_ref() {
+            Term::Abstraction(var, _, body) => Some((**body).clone().substitute(var, right_term)),
+            _ => match step(left_term) {
+                Some(left_term) => Some(Term::Application(Box::new(left_term), right_term.clone())),
+                None => step(right_term)
+                    .map(|right_term| Term::Application(left_term.clone(), Box::new(right_term))),
+            },
+        },
+        Term::Abstraction(var, typ, body) | Term::Pi(var, typ, body) => {
+            let (typ, body) = match step(typ) {
+                Some(typ) => (Box::new(typ), body.clone()),
+                None => (typ.clone(), Box::new(step(body)?)),
+            };
+            match term {
+                Term::Abstraction(_, _, _) => Some(Term::Abstraction(var.clone(), typ, body)),
+                _ => Some(Term::Pi(var.clone(), typ, body)),
+            }
+        }
+    }
+}
+
+/// Reduce the term to its β-normal form, with the reducer of the simply typed terms
+fn normalise(term: &Term) -> CocTypeResult<Term> {
+    normalise_with(term.clone(), step).map_err(CocTypeError::Reduce)
+}
+
+/// Whether the terms are equal up to the names of their bound variables
+fn alpha_equivalent(t1: &Term, t2: &Term, bound: &mut Vec<(String, String)>) -> bool {
+    match (t1, t2) {
+        (Term::Variable(a), Term::Variable(b)) => {
+            // the innermost binding of either name decides, free variables are compared by name
+            match bound.iter().rev().find(|(x, y)| x == a || y == b) {
+                Some((x, y)) => x == a && y == b,
+                None => a == b,
+            }
+        }
+        (Term::Sort(a), Term::Sort(b)) => a == b,
+        (Term::Application(f1, a1), Term::Application(f2, a2)) => {
+            alpha_equivalent(f1, f2, bound) && alpha_equivalent(a1, a2, bound)
+        }
+        (Term::Abstraction(x, typ1, body1), Term::Abstraction(y, typ2, body2))
+        | (Term::Pi(x, typ1, body1), Term::Pi(y, typ2, body2)) => {
+            if !alpha_equivalent(typ1, typ2, bound) {
+                return false;
+            }
+            bound.push((x.clone(), y.clone()));
+            let equivalent = alpha_equivalent(body1, body2, bound);
+            bound.pop();
+            equivalent
+        }
+        _ => false,
+    }
+}
+
+/// Whether the terms are β-equivalent: their normal forms are equal (up to bound variable names)
+fn convertible(t1: &Term, t2: &Term) -> CocTypeResult<bool> {
+    Ok(alpha_equivalent(
+        &normalise(t1)?,
+        &normalise(t2)?,
+        &mut Vec::new(),
+    ))
+}
+
+/// The sort of a term that is used as a type, e.g. the type of a bound variable
+fn sort_of(term: &Term, context: &mut TermContext) -> CocTypeResult<Sort> {
+    let typ = synthesise(term, context)?;
+    match normalise(&typ)? {
+        Term::Sort(sort) => Ok(sort),
+        typ => Err(CocTypeError::NotAType(
+            Box::new(term.clone()),
+            Box::new(typ),
+        )),
+    }
+}
+
+/// Whether the name is bound in the context, or occurs free in one of its types
+fn in_context(name: &str, context: &TermContext) -> bool {
+    context
+        .iter()
+        .any(|(var, typ)| var == name || occurs_free(name, typ))
+}
+
+/// The variable of a binder and the body it binds in, renamed to a fresh name if the variable is in the context. \
+/// Otherwise the binder would shadow a variable that the types in the context refer to,
+/// e.g. in λx:A. ΛA:*. x the type A of x is not the A bound by the inner Λ
+fn fresh_binder(var: &str, body: &Term, context: &TermContext) -> (String, Term) {
+    if !in_context(var, context) {
+        return (var.to_string(), body.clone());
+    }
+    let new_name = unique_varname(var, |name| {
+        in_context(name, context) || occurs_free(name, body)
+    });
+    let body = body
+        .clone()
+        .substitute(var, &Term::Variable(new_name.clone()));
+    (new_name, body)
+}
+
+/// Add the variable to the checked context. An earlier binding of the same variable is renamed to a fresh name,
+/// also in the types after it, so those types still refer to it and not to the new binding
+fn bind(context: &mut TermContext, var: &str, typ: Term) {
+    if let Some(earlier) = context.iter().rposition(|(name, _)| name == var) {
+        let new_name = unique_varname(var, |name| in_context(name, context));
+        let renamed = Term::Variable(new_name.clone());
+        context[earlier].0 = new_name;
+        for (_, later) in &mut context[earlier + 1..] {
+            *later = later.clone().substitute(var, &renamed);
+        }
+    }
+    context.push((var.to_string(), typ));
+}
+
+/// Synthesise the type of a term in the given context
+///
+/// Var: x has the type it is bound with \
+/// Sort: * : □ \
+/// Pi: if A : s1, and B : s2 with x : A, then Πx:A. B : s2 (for all four combinations of sorts) \
+/// Abs: if A : s, b : B with x : A, and Πx:A. B is a type, then λx:A. b : Πx:A. B \
+/// App: if f : Πx:A. B and a : A' where A and A' are β-equivalent, then f a : B[x := a]
+fn synthesise(term: &Term, context: &mut TermContext) -> CocTypeResult<Term> {
+    match term {
+        Term::Variable(name) => match context.iter().rev().find(|(var, _)| var == name) {
+            Some((_, typ)) => Ok(typ.clone()),
+            None => Err(CocTypeError::UnknownType(name.clone())),
+        },
+        Term::Sort(Sort::Star) => Ok(Term::Sort(Sort::Square)),
+        Term::Sort(Sort::Square) => Err(CocTypeError::SquareHasNoType),
+        Term::Pi(var, typ, body) => {
+            sort_of(typ, context)?;
+            let (var, body) = fresh_binder(var, body, context);
+            context.push((var, *typ.clone()));
+            let sort = sort_of(&body, context);
+            context.pop();
+            Ok(Term::Sort(sort?))
+        }
+        Term::Abstraction(var, typ, body) => {
+            sort_of(typ, context)?;
+            let (var, body) = fresh_binder(var, body, context);
+            context.push((var.clone(), *typ.clone()));
+            let body_type = synthesise(&body, context);
+            context.pop();
+            let pi = Term::Pi(var, typ.clone(), Box::new(body_type?));
+            // e.g. λA:*. * has no type, since Π A:*. □ is not a type
+            sort_of(&pi, context)?;
+            Ok(pi)
+        }
+        Term::Application(left_term, right_term) => {
+            let function_type = synthesise(left_term, context)?;
+            match normalise(&function_type)? {
+                Term::Pi(var, domain, codomain) => {
+                    let argument_type = synthesise(right_term, context)?;
+                    if !convertible(&domain, &argument_type)? {
+                        return Err(CocTypeError::MismatchedTypes(
+                            domain,
+                            Box::new(argument_type),
+                            right_term.clone(),
+                        ));
+                    }
+                    Ok(codomain.substitute(&var, right_term))
+                }
+                typ => Err(CocTypeError::NotAFunction(left_term.clone(), Box::new(typ))),
+            }
+        }
+    }
+}
+
+/// Returns the normal form of the type of the term in the context,
+/// after checking that the context is well-formed and that the term has the expected type (if given).
+fn _type_check(context: &TermContext, term: &Term, expected: Option<&Term>) -> CocTypeResult<Term> {
+    reset_counters();
+
+    // every type in the context is a type in the context before it
+    let mut checked = TermContext::new();
+    for (var, typ) in context {
+        sort_of(typ, &mut checked)?;
+        bind(&mut checked, var, typ.clone());
+    }
+
+    let typ = synthesise(term, &mut checked)?;
+    dbg!(&typ);
+    if let Some(expected) = expected {
+        // □ is not a term, but it is the type of a kind
+        if *expected != Term::Sort(Sort::Square) {
+            sort_of(expected, &mut checked)?;
+        }
+        if !convertible(expected, &typ)? {
+            return Err(CocTypeError::MismatchedTypes(
+                Box::new(expected.clone()),
+                Box::new(typ),
+                Box::new(term.clone()),
+            ));
+        }
+    }
+    normalise(&typ)
+}
+
+/// Typecheck the term of a calculus of constructions judgement, in its context
+/// If the term does not have the expected type (or no type at all), prints an error and exits the program
+///
+/// # Arguments
+/// * `context` - The types of the free variables of the term
+/// * `term` - The term to typecheck
+/// * `expected` - The type of the judgement, if it has one
+/// * `idx` - The index of the line the term is on (for error printing)
+///
+/// # Returns
+/// The normal form of the type of the term
+///
+/// # Error
+/// "Invalid term [{err_code}] caught during typechecking on line {idx}!"
+pub(crate) fn coc_type_check(
+    context: &TermContext,
+    term: &Term,
+    expected: Option<&Term>,
+    idx: usize,
+) -> Term {
+    match _type_check(context, term, expected) {
+        Ok(typ) => typ,
+        Err(err_code) => {
+            eprintln!(
+                "Invalid term [{}] caught during typechecking on line {}!",
+                err_code,
+                idx + 1
+            );
+            exit(Failure::Type);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coc::{parse_line, Line},
+        tokenizer::manual_tokenize,
+    };
+
+    /// Typecheck a judgement line, returns the normal form of its type
+    fn check(line: &str) -> CocTypeResult<Term> {
+        let tokens = manual_tokenize(line).unwrap();
+        let Line::Judgement(context, term, expected) = parse_line(&tokens, 0) else {
+            panic!("not a judgement: {}", line);
+        };
+        _type_check(&context, &term, expected.as_ref())
+    }
+
+    #[test]
+    fn polymorphic_identity() {
+        assert!(check("ΛA:*. λx:A. x : ΠA:*. A -> A").is_ok());
+        assert!(check("A : *, x : A ⊢ x : A").is_ok());
+    }
+
+    #[test]
+    fn shadowing_binder_does_not_capture() {
+        // the inner A is another type than the type of x
+        assert!(check("ΛA:*. λx:A. ΛA:*. x : ΠA:*. A -> ΠB:*. B").is_err());
+        assert!(check("ΛA:*. λx:A. ΛA:*. x : ΠA:*. A -> ΠB:*. A").is_ok());
+        // the type of x is the first A, not the A that shadows it
+        assert!(check("A : *, x : A, A : * ⊢ x : A").is_err());
+    }
+
+    #[test]
+    fn conversion_renames_without_capture() {
+        // (λx:*. Πy:*. y1 -> x) y is Πy2:*. y1 -> y, the bound y is not renamed to the free y1
+        let typ = "(λx:*. Πy:*. y1 -> x) y";
+        assert!(check(&format!("y : *, y1 : *, f : Πz:*. z -> y ⊢ f : {}", typ)).is_err());
+        assert!(check(&format!("y : *, y1 : *, f : Πz:*. y1 -> y ⊢ f : {}", typ)).is_ok());
+    }
+}
@@ -0,0 +1,80 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    coc::{parse_line, Line, Term},
+    coc_checker::coc_type_check,
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    reducer::Substitute,
+    tokenizer::tokenize,
+    utils::mode_arguments,
+};
+
+/// Replace the defined names by their definitions, except for the names bound in the context
+fn unfold(term: &Term, definitions: &[(String, Term)], bound: &[String]) -> Term {
+    definitions
+        .iter()
+        .rev()
+        .filter(|(name, _)| !bound.contains(name))
+        .fold(term.clone(), |term, (name, definition)| {
+            term.substitute(name, definition)
+        })
+}
+
+/// Calculus of constructions mode: typecheck every line in the file (or stdin) as a dependently typed term. \
+/// A line is a definition `name := term`, which the following lines can use,
+/// or a judgement `context ⊢ term : type`, where the context and the type are optional. \
+/// Prints every line with the normal form of its type, e.g. `ΛA:*. λx:A. x` prints `ΛA:*. λx:A. x : ΠA:*. A -> A`. \
+/// `-c [file]`
+pub(super) fn coc_mode(args: Vec<String>) {
+    let arguments = mode_arguments(
+        &args,
+        "Usage: lc -c [FILE]\n\nTypecheck every line of FILE, or stdin if there is none, in the calculus of constructions",
+        &[],
+        &[],
+    );
+    let lines = match arguments.file {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+
+    // the definitions so far, with the definitions they use already unfolded
+    let mut definitions: Vec<(String, Term)> = Vec::new();
+    let checked: Vec<String> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            let line = parse_line(&tokens, idx);
+            match &line {
+                Line::Definition(name, term) => {
+                    let term = unfold(term, &definitions, &[]);
+                    let typ = coc_type_check(&Vec::new(), &term, None, idx);
+                    definitions.push((name.clone(), term));
+                    format!("{} : {}", line, typ)
+                }
+                Line::Judgement(context, term, expected) => {
+                    // a variable in the context shadows a definition with the same name
+                    let mut bound = Vec::new();
+                    let mut unfolded_context = Vec::new();
+                    for (var, typ) in context {
+                        unfolded_context.push((var.clone(), unfold(typ, &definitions, &bound)));
+                        bound.push(var.clone());
+                    }
+                    let term = unfold(term, &definitions, &bound);
+                    let expected = expected
+                        .as_ref()
+                        .map(|expected| unfold(expected, &definitions, &bound));
+                    let typ = coc_type_check(&unfolded_context, &term, expected.as_ref(), idx);
+                    match expected {
+                        Some(_) => line.to_string(),
+                        None => format!("{} : {}", line, typ),
+                    }
+                }
+            }
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the checked lines
+    for line in checked {
+        println!("{}", line);
+    }
+}
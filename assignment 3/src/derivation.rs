@@ -0,0 +1,386 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    parser::{Context, Expression, Type},
+    printer::{pretty, proposition_string, type_string, PrintConfig},
+};
+
+/// How the judgements and rules of a derivation are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Notation {
+    /// Typing judgements `Γ ⊢ e : A` with the typing rules
+    Typing,
+    /// Logical judgements `Γ ⊢ A` of propositions with the natural deduction rules,
+    /// the Curry-Howard reading of the typing derivation
+    Logic,
+}
+
+/// The typing rule a judgement in a derivation is concluded by
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Rule {
+    Var,
+    Abs,
+    App,
+    Let,
+    TAbs,
+    TApp,
+    Unit,
+    Pair,
+    Fst,
+    Snd,
+    Inl,
+    Inr,
+    Case,
+    Anno,
+    Zero,
+    Succ,
+    Pred,
+    IsZero,
+    True,
+    False,
+    If,
+    Fix,
+    Absurd,
+}
+
+impl Rule {
+    fn name(&self) -> &'static str {
+        match self {
+            Rule::Var => "Var",
+            Rule::Abs => "Abs",
+            Rule::App => "App",
+            Rule::Let => "Let",
+            Rule::TAbs => "TAbs",
+            Rule::TApp => "TApp",
+            Rule::Unit => "Unit",
+            Rule::Pair => "Pair",
+            Rule::Fst => "Fst",
+            Rule::Snd => "Snd",
+            Rule::Inl => "Inl",
+            Rule::Inr => "Inr",
+            Rule::Case => "Case",
+            Rule::Anno => "Anno",
+            Rule::Zero => "Zero",
+            Rule::Succ => "Succ",
+            Rule::Pred => "Pred",
+            Rule::IsZero => "IsZero",
+            Rule::True => "True",
+            Rule::False => "False",
+            Rule::If => "If",
+            Rule::Fix => "Fix",
+            Rule::Absurd => "Absurd",
+        }
+    }
+
+    /// The name of the natural deduction rule the typing rule corresponds to,
+    /// `negation` if the implication it introduces or eliminates is a negation A -> ⊥. \
+    /// Let is a cut (proving a lemma before using it), rules without a logical reading keep their name
+    fn logic_name(&self, negation: bool) -> &'static str {
+        match self {
+            Rule::Var => "Ax",
+            Rule::Abs if negation => "¬I",
+            Rule::Abs => "→I",
+            Rule::App if negation => "¬E",
+            Rule::App => "→E",
+            Rule::Let => "Cut",
+            Rule::TAbs => "∀I",
+            Rule::TApp => "∀E",
+            Rule::Unit => "⊤I",
+            Rule::Pair => "∧I",
+            Rule::Fst => "∧E₁",
+            Rule::Snd => "∧E₂",
+            Rule::Inl => "∨I₁",
+            Rule::Inr => "∨I₂",
+            Rule::Case => "∨E",
+            Rule::Absurd => "⊥E",
+            _ => self.name(),
+        }
+    }
+}
+
+/// A natural deduction derivation of the typing judgement `context ⊢ expr : typ`
+#[derive(Debug, Clone)]
+pub(crate) struct Derivation {
+    pub(crate) rule: Rule,
+    pub(crate) context: Context,
+    pub(crate) expr: Expression,
+    pub(crate) typ: Type,
+    /// The derivations of the premises of the rule, from left to right
+    pub(crate) premises: Vec<Derivation>,
+}
+
+impl Derivation {
+    /// The conclusion of the derivation, e.g. `x : A ⊢ x : A`, or `A ⊢ A` as a proposition
+    fn conclusion(&self, notation: Notation) -> String {
+        if notation == Notation::Logic {
+            let hypotheses = self
+                .context
+                .iter()
+                .map(|(_, typ)| proposition_string(typ))
+                .collect::<Vec<String>>();
+            let proposition = format!("⊢ {}", proposition_string(&self.typ));
+            return if hypotheses.is_empty() {
+                proposition
+            } else {
+                format!("{} {}", hypotheses.join(", "), proposition)
+            };
+        }
+        let context = self
+            .context
+            .iter()
+            .map(|(var, typ)| format!("{} : {}", var, type_string(typ)))
+            .collect::<Vec<String>>();
+        let judgement = format!(
+            "⊢ {} : {}",
+            pretty(&self.expr, &PrintConfig::default()),
+            type_string(&self.typ)
+        );
+        if context.is_empty() {
+            judgement
+        } else {
+            format!("{} {}", context.join(", "), judgement)
+        }
+    }
+
+    /// The name of the rule that concludes the derivation
+    fn rule_name(&self, notation: Notation) -> &'static str {
+        match notation {
+            Notation::Typing => self.rule.name(),
+            Notation::Logic => {
+                // the implication is the type of the abstraction, or of the applied function
+                let implication = match self.rule {
+                    Rule::App => &self.premises[0].typ,
+                    _ => &self.typ,
+                };
+                let negation =
+                    matches!(implication, Type::Function(_, codomain) if **codomain == Type::Empty);
+                self.rule.logic_name(negation)
+            }
+        }
+    }
+
+    /// An ascription proves the same proposition as its premise, so as a proof it is left out
+    fn skipped(&self, notation: Notation) -> Option<&Derivation> {
+        match (notation, self.rule) {
+            (Notation::Logic, Rule::Anno) => self.premises.first(),
+            _ => None,
+        }
+    }
+
+    /// The lines of the proof tree, all padded to the same width,
+    /// and the width of its bar (without the name of the rule)
+    fn tree_lines(&self, notation: Notation) -> (Vec<String>, usize) {
+        if let Some(premise) = self.skipped(notation) {
+            return premise.tree_lines(notation);
+        }
+        // the premises next to each other, aligned at the bottom
+        let premises = self
+            .premises
+            .iter()
+            .map(|premise| premise.tree_lines(notation))
+            .collect::<Vec<(Vec<String>, usize)>>();
+        let height = premises
+            .iter()
+            .map(|(lines, _)| lines.len())
+            .max()
+            .unwrap_or(0);
+        let mut lines = vec![String::new(); height];
+        // the bar spans the premises, up to the name of the rule of the last one
+        let mut premises_width = 0;
+        for (i, (premise, bar)) in premises.iter().enumerate() {
+            let premise_width = width(&premise[0]);
+            let offset = height - premise.len();
+            for (row, line) in lines.iter_mut().enumerate() {
+                if i > 0 {
+                    line.push_str("   ");
+                }
+                match row.checked_sub(offset) {
+                    Some(row) => line.push_str(&premise[row]),
+                    None => line.push_str(&" ".repeat(premise_width)),
+                }
+            }
+            premises_width = width(&lines[0]) - premise_width + bar;
+        }
+
+        let conclusion = self.conclusion(notation);
+        let bar = premises_width.max(width(&conclusion));
+        lines.push(format!(
+            "{} ({})",
+            "-".repeat(bar),
+            self.rule_name(notation)
+        ));
+        lines.push(conclusion);
+
+        let max = lines.iter().map(|line| width(line)).max().unwrap_or(0);
+        let lines = lines
+            .into_iter()
+            .map(|line| {
+                let padding = max - width(&line);
+                line + &" ".repeat(padding)
+            })
+            .collect();
+        (lines, bar)
+    }
+
+    /// The derivation as a proof tree, with the conclusion at the bottom
+    pub(crate) fn tree(&self, notation: Notation) -> String {
+        self.tree_lines(notation)
+            .0
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+
+    /// The bussproofs commands of the derivation, premises first
+    fn latex_lines(&self, notation: Notation, lines: &mut Vec<String>) {
+        if let Some(premise) = self.skipped(notation) {
+            return premise.latex_lines(notation, lines);
+        }
+        for premise in &self.premises {
+            premise.latex_lines(notation, lines);
+        }
+        // an axiom has no premises, but bussproofs needs one to draw the line above it
+        if self.premises.is_empty() {
+            lines.push("\\AxiomC{}".to_string());
+        }
+        let inference = match self.premises.len() {
+            0 | 1 => "UnaryInfC",
+            2 => "BinaryInfC",
+            _ => "TrinaryInfC",
+        };
+        lines.push(format!(
+            "\\RightLabel{{\\scriptsize {}}}",
+            latex_label(self.rule_name(notation))
+        ));
+        lines.push(format!(
+            "\\{}{{${}$}}",
+            inference,
+            latex(&self.conclusion(notation))
+        ));
+    }
+
+    /// The derivation as LaTeX source for the bussproofs package
+    pub(crate) fn latex(&self, notation: Notation) -> String {
+        let mut lines = Vec::new();
+        self.latex_lines(notation, &mut lines);
+        let body = lines
+            .iter()
+            .map(|line| format!("  {}", line))
+            .collect::<Vec<String>>();
+        format!(
+            "\\begin{{prooftree}}\n{}\n\\end{{prooftree}}",
+            body.join("\n")
+        )
+    }
+}
+
+/// The width of a line on the terminal
+fn width(line: &str) -> usize {
+    line.chars().count()
+}
+
+/// Whether the character can end (or start) a term, so a space next to it is an application
+fn is_term_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '(' | ')' | '[' | ']' | 'λ' | 'Λ')
+}
+
+/// Convert a printed judgement to LaTeX math mode
+fn latex(s: &str) -> String {
+    let chars = s.chars().collect::<Vec<char>>();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            'λ' => out.push_str("\\lambda "),
+            'Λ' => out.push_str("\\Lambda "),
+            '∀' => out.push_str("\\forall "),
+            '∧' => out.push_str("\\land"),
+            '∨' => out.push_str("\\lor"),
+            '¬' => out.push_str("\\neg "),
+            '⊥' => out.push_str("\\bot "),
+            '⊤' => out.push_str("\\top "),
+            '⊢' => out.push_str("\\vdash"),
+            '-' if chars.get(i + 1) == Some(&'>') => {
+                out.push_str("\\to");
+                i += 1;
+            }
+            '=' if chars.get(i + 1) == Some(&'>') => {
+                out.push_str("\\Rightarrow");
+                i += 1;
+            }
+            '*' => out.push_str("\\times"),
+            '|' => out.push_str("\\mid"),
+            '^' => {
+                // the type of a bound variable is a single variable or a parenthesised type
+                let start = i + 1;
+                let mut end = start;
+                let mut depth = 0;
+                while end < chars.len() {
+                    match chars[end] {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        c if depth == 0 && !c.is_alphanumeric() => break,
+                        _ => {}
+                    }
+                    end += 1;
+                    if depth == 0 && chars[end - 1] == ')' {
+                        break;
+                    }
+                }
+                let typ = chars[start..end].iter().collect::<String>();
+                out.push_str(&format!("^{{{}}}", latex(&typ)));
+                i = end;
+                continue;
+            }
+            ' ' => {
+                // math mode ignores spaces, so the space of an application has to be explicit
+                let before = i.checked_sub(1).map(|j| chars[j]);
+                let after = chars.get(i + 1).copied();
+                match (before, after) {
+                    (Some(b), Some(a)) if is_term_char(b) && is_term_char(a) => out.push_str("\\;"),
+                    _ => out.push(' '),
+                }
+            }
+            c if c.is_alphanumeric() => {
+                let start = i;
+                while i < chars.len() && chars[i].is_alphanumeric() {
+                    i += 1;
+                }
+                let name = chars[start..i].iter().collect::<String>();
+                match name.as_str() {
+                    "let" | "in" | "fst" | "snd" | "inl" | "inr" | "case" | "of" | "Unit"
+                    | "succ" | "pred" | "iszero" | "true" | "false" | "if" | "then" | "else"
+                    | "fix" | "absurd" | "Nat" | "Bool" | "Empty" => {
+                        out.push_str(&format!("\\mathsf{{{}}}", name))
+                    }
+                    _ if name.chars().count() > 1 => out.push_str(&format!("\\mathit{{{}}}", name)),
+                    _ => out.push_str(&name),
+                }
+                continue;
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Convert the name of a rule to LaTeX text mode, e.g. `∧E₁` to `$\land$E$_1$`
+fn latex_label(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '→' => "$\\to$".to_string(),
+            '∧' => "$\\land$".to_string(),
+            '∨' => "$\\lor$".to_string(),
+            '¬' => "$\\neg$".to_string(),
+            '⊥' => "$\\bot$".to_string(),
+            '⊤' => "$\\top$".to_string(),
+            '∀' => "$\\forall$".to_string(),
+            '₁' => "$_1$".to_string(),
+            '₂' => "$_2$".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,60 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    derivation::{Derivation, Notation},
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::{is_judgement, parse, parse_open_expression},
+    tokenizer::tokenize,
+    type_checker::{synthesise_type, type_check},
+    utils::mode_arguments,
+};
+
+/// Derivation mode: print the typing derivation of every judgement in the file (or stdin),
+/// as an ASCII proof tree, or as LaTeX `bussproofs` source with `--latex`. \
+/// Like in synthesis mode, the `: type` part can be left out. \
+/// With `--logic` the derivation is printed as a natural deduction proof of the proposition
+/// the type stands for, with the logical rule names (→I, →E, ∧I, ...). \
+/// `-d [--latex] [--logic] [file]`
+pub(super) fn derivation_mode(args: Vec<String>) {
+    let arguments = mode_arguments(
+        &args,
+        "Usage: lc -d [--latex] [--logic] [FILE]\n\nPrint the typing derivation of every judgement (or annotated expression) in FILE, or stdin if there is none\n\nOptions:\n  --latex  Print the derivations as LaTeX, for the bussproofs package\n  --logic  Print the derivations as natural deduction proofs",
+        &["--latex", "--logic"],
+        &[],
+    );
+    let latex = arguments.has("--latex");
+    let notation = match arguments.has("--logic") {
+        true => Notation::Logic,
+        false => Notation::Typing,
+    };
+
+    let lines = match arguments.file {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+    let derivations: Vec<Derivation> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            if is_judgement(&tokens) {
+                type_check(&parse(&tokens, idx), idx)
+            } else {
+                let (context, expression) = parse_open_expression(&tokens, idx);
+                synthesise_type(&expression, &context, idx)
+            }
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the derivations
+    let derivations = derivations
+        .iter()
+        .map(|derivation| {
+            if latex {
+                derivation.latex(notation)
+            } else {
+                derivation.tree(notation)
+            }
+        })
+        .collect::<Vec<String>>();
+    println!("{}", derivations.join("\n\n"));
+}
@@ -0,0 +1,367 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::parser::{Expression, Judgement, Type};
+
+/// The format the tree of the parser is dumped in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DumpFormat {
+    /// A Graphviz graph
+    Dot,
+    /// An S-expression
+    Sexp,
+}
+
+impl DumpFormat {
+    pub(crate) fn from_name(name: &str) -> Option<DumpFormat> {
+        match name {
+            "dot" => Some(DumpFormat::Dot),
+            "sexp" => Some(DumpFormat::Sexp),
+            _ => None,
+        }
+    }
+}
+
+/// A Graphviz graph that is built up one node and edge at a time,
+/// with the variables that are in scope and the node that binds them (the innermost last)
+struct Dot {
+    lines: Vec<String>,
+    nodes: usize,
+    terms: Vec<(String, usize)>,
+    types: Vec<(String, usize)>,
+}
+
+impl Dot {
+    /// Add a node with the label and extra attributes (e.g. `shape=box`), returns its id
+    fn node(&mut self, label: &str, attributes: &str) -> usize {
+        let id = self.nodes;
+        self.nodes += 1;
+        let label = label.replace('\\', "\\\\").replace('"', "\\\"");
+        match attributes {
+            "" => self.lines.push(format!("  n{} [label=\"{}\"];", id, label)),
+            _ => self
+                .lines
+                .push(format!("  n{} [label=\"{}\", {}];", id, label, attributes)),
+        }
+        id
+    }
+
+    /// Add an edge from a node to its child
+    fn edge(&mut self, parent: usize, child: usize) {
+        self.lines.push(format!("  n{} -> n{};", parent, child));
+    }
+
+    /// Add a node with edges to its children, in order
+    fn parent(&mut self, label: &str, attributes: &str, children: &[usize]) -> usize {
+        let id = self.node(label, attributes);
+        for child in children {
+            self.edge(id, *child);
+        }
+        id
+    }
+
+    /// Add a (type) variable with a dashed edge from the node that binds it (which does not affect the layout),
+    /// or in red if it is free
+    fn variable(&mut self, var: &str, is_type: bool, binder: Option<usize>) -> usize {
+        let Some(binder) = binder else {
+            let attributes = if is_type {
+                "shape=box, color=red"
+            } else {
+                "color=red"
+            };
+            return self.node(var, attributes);
+        };
+        let id = self.node(var, if is_type { "shape=box" } else { "" });
+        self.lines.push(format!(
+            "  n{} -> n{} [style=dashed, color=blue, constraint=false];",
+            binder, id
+        ));
+        id
+    }
+
+    /// Add the nodes of the type (as boxes), returns the id of its root
+    fn typ(&mut self, typ: &Type) -> usize {
+        match typ {
+            Type::Variable(var) => {
+                let binder = self.types.iter().rev().find(|(name, _)| name == var);
+                let binder = binder.map(|(_, binder)| *binder);
+                self.variable(var, true, binder)
+            }
+            Type::Function(domain, codomain) => {
+                let children = [self.typ(domain), self.typ(codomain)];
+                self.parent("->", "shape=box", &children)
+            }
+            Type::Product(left, right) => {
+                let children = [self.typ(left), self.typ(right)];
+                self.parent("*", "shape=box", &children)
+            }
+            Type::Sum(left, right) => {
+                let children = [self.typ(left), self.typ(right)];
+                self.parent("+", "shape=box", &children)
+            }
+            Type::ForAll(var, body) => {
+                let id = self.node(&format!("∀{}", var), "shape=box");
+                self.types.push((var.clone(), id));
+                let body = self.typ(body);
+                self.types.pop();
+                self.edge(id, body);
+                id
+            }
+            Type::Unknown(_) | Type::Unit | Type::Nat | Type::Bool | Type::Empty => {
+                self.node(&typ.to_string(), "shape=box")
+            }
+        }
+    }
+
+    /// Add the nodes of an operator with an optional type and an operand, e.g. `inl^(A + B) a`
+    fn annotated(&mut self, label: &str, typ: &Option<Box<Type>>, expr: &Expression) -> usize {
+        let mut children = Vec::new();
+        if let Some(typ) = typ {
+            children.push(self.typ(typ));
+        }
+        children.push(self.expression(expr));
+        self.parent(label, "", &children)
+    }
+
+    /// Add the nodes of the expression (whose variables are bound by the node), returns the id of its root
+    fn bound_expression(&mut self, var: &str, binder: usize, expr: &Expression) -> usize {
+        self.terms.push((var.to_string(), binder));
+        let id = self.expression(expr);
+        self.terms.pop();
+        id
+    }
+
+    /// Add the nodes of the expression, returns the id of its root
+    fn expression(&mut self, expr: &Expression) -> usize {
+        match expr {
+            Expression::Variable(var) => {
+                let binder = self.terms.iter().rev().find(|(name, _)| name == var);
+                let binder = binder.map(|(_, binder)| *binder);
+                self.variable(var, false, binder)
+            }
+            Expression::Application(lexpr, rexpr) => {
+                let children = [self.expression(lexpr), self.expression(rexpr)];
+                self.parent("@", "", &children)
+            }
+            Expression::Abstraction(var, typ, body) => {
+                let id = self.node(&format!("λ{}", var), "");
+                if let Some(typ) = typ {
+                    let typ = self.typ(typ);
+                    self.edge(id, typ);
+                }
+                let body = self.bound_expression(var, id, body);
+                self.edge(id, body);
+                id
+            }
+            Expression::Let(var, value, body) => {
+                let id = self.node(&format!("let {}", var), "");
+                let value = self.expression(value);
+                let body = self.bound_expression(var, id, body);
+                self.edge(id, value);
+                self.edge(id, body);
+                id
+            }
+            Expression::TypeAbstraction(var, body) => {
+                let id = self.node(&format!("Λ{}", var), "");
+                self.types.push((var.clone(), id));
+                let body = self.expression(body);
+                self.types.pop();
+                self.edge(id, body);
+                id
+            }
+            Expression::TypeApplication(expr, typ) => {
+                let children = [self.expression(expr), self.typ(typ)];
+                self.parent("@[]", "", &children)
+            }
+            Expression::Pair(left, right) => {
+                let children = [self.expression(left), self.expression(right)];
+                self.parent("(,)", "", &children)
+            }
+            Expression::Case(expr, left_var, left, right_var, right) => {
+                let expr = self.expression(expr);
+                let left_branch = self.node(&format!("inl {}", left_var), "");
+                let left = self.bound_expression(left_var, left_branch, left);
+                self.edge(left_branch, left);
+                let right_branch = self.node(&format!("inr {}", right_var), "");
+                let right = self.bound_expression(right_var, right_branch, right);
+                self.edge(right_branch, right);
+                self.parent("case", "", &[expr, left_branch, right_branch])
+            }
+            Expression::Ascription(expr, typ) => {
+                let children = [self.expression(expr), self.typ(typ)];
+                self.parent(":", "", &children)
+            }
+            Expression::If(condition, then, otherwise) => {
+                let children = [
+                    self.expression(condition),
+                    self.expression(then),
+                    self.expression(otherwise),
+                ];
+                self.parent("if", "", &children)
+            }
+            Expression::Left(typ, expr) => self.annotated("inl", typ, expr),
+            Expression::Right(typ, expr) => self.annotated("inr", typ, expr),
+            Expression::Absurd(typ, expr) => self.annotated("absurd", typ, expr),
+            Expression::First(expr) => self.annotated("fst", &None, expr),
+            Expression::Second(expr) => self.annotated("snd", &None, expr),
+            Expression::Succ(expr) => self.annotated("succ", &None, expr),
+            Expression::Pred(expr) => self.annotated("pred", &None, expr),
+            Expression::IsZero(expr) => self.annotated("iszero", &None, expr),
+            Expression::Fix(expr) => self.annotated("fix", &None, expr),
+            Expression::Unit => self.node("()", ""),
+            Expression::Zero => self.node("0", ""),
+            Expression::True => self.node("true", ""),
+            Expression::False => self.node("false", ""),
+        }
+    }
+
+    /// Add the nodes of the judgement under a `⊢` node: a node for every variable of the context
+    /// (which binds its occurrences), the expression and the type
+    fn judgement(&mut self, judgement: &Judgement) -> usize {
+        let Judgement::Judgement(context, expr, typ) = judgement;
+        let mut children = Vec::new();
+        for (var, typ) in context {
+            let typ = self.typ(typ);
+            let binding = self.parent(&format!("{} :", var), "", &[typ]);
+            self.terms.push((var.clone(), binding));
+            children.push(binding);
+        }
+        children.push(self.expression(expr));
+        children.push(self.typ(typ));
+        self.terms.clear();
+        self.parent("⊢", "", &children)
+    }
+
+    /// The graph with the given name
+    fn graph(&self, name: &str) -> String {
+        format!(
+            "digraph {} {{\n  ordering=out;\n{}\n}}",
+            name,
+            self.lines.join("\n")
+        )
+    }
+}
+
+fn new_dot() -> Dot {
+    Dot {
+        lines: Vec::new(),
+        nodes: 0,
+        terms: Vec::new(),
+        types: Vec::new(),
+    }
+}
+
+/// The type as an S-expression, e.g. `∀X. X -> X` is `(∀ X (-> X X))`
+fn type_sexp(typ: &Type) -> String {
+    match typ {
+        Type::Function(domain, codomain) => {
+            format!("(-> {} {})", type_sexp(domain), type_sexp(codomain))
+        }
+        Type::ForAll(var, body) => format!("(∀ {} {})", var, type_sexp(body)),
+        Type::Product(left, right) => format!("(* {} {})", type_sexp(left), type_sexp(right)),
+        Type::Sum(left, right) => format!("(+ {} {})", type_sexp(left), type_sexp(right)),
+        Type::Variable(_)
+        | Type::Unknown(_)
+        | Type::Unit
+        | Type::Nat
+        | Type::Bool
+        | Type::Empty => typ.to_string(),
+    }
+}
+
+/// An operator with an optional type and an operand, e.g. `(inl (+ A B) a)` or `(inl a)`
+fn annotated_sexp(label: &str, typ: &Option<Box<Type>>, expr: &Expression) -> String {
+    match typ {
+        Some(typ) => format!("({} {} {})", label, type_sexp(typ), expression_sexp(expr)),
+        None => format!("({} {})", label, expression_sexp(expr)),
+    }
+}
+
+/// The expression as an S-expression, e.g. `λx^A.x y` is `(λ (x A) (@ x y))`
+fn expression_sexp(expr: &Expression) -> String {
+    let sexp = expression_sexp;
+    match expr {
+        Expression::Variable(var) => var.clone(),
+        Expression::Application(lexpr, rexpr) => format!("(@ {} {})", sexp(lexpr), sexp(rexpr)),
+        Expression::Abstraction(var, None, body) => format!("(λ {} {})", var, sexp(body)),
+        Expression::Abstraction(var, Some(typ), body) => {
+            format!("(λ ({} {}) {})", var, type_sexp(typ), sexp(body))
+        }
+        Expression::Let(var, value, body) => {
+            format!("(let {} {} {})", var, sexp(value), sexp(body))
+        }
+        Expression::TypeAbstraction(var, body) => format!("(Λ {} {})", var, sexp(body)),
+        Expression::TypeApplication(expr, typ) => {
+            format!("(@[] {} {})", sexp(expr), type_sexp(typ))
+        }
+        Expression::Pair(left, right) => format!("(pair {} {})", sexp(left), sexp(right)),
+        Expression::Case(expr, left_var, left, right_var, right) => format!(
+            "(case {} (inl {} {}) (inr {} {}))",
+            sexp(expr),
+            left_var,
+            sexp(left),
+            right_var,
+            sexp(right)
+        ),
+        Expression::Ascription(expr, typ) => format!("(: {} {})", sexp(expr), type_sexp(typ)),
+        Expression::If(condition, then, otherwise) => format!(
+            "(if {} {} {})",
+            sexp(condition),
+            sexp(then),
+            sexp(otherwise)
+        ),
+        Expression::Left(typ, expr) => annotated_sexp("inl", typ, expr),
+        Expression::Right(typ, expr) => annotated_sexp("inr", typ, expr),
+        Expression::Absurd(typ, expr) => annotated_sexp("absurd", typ, expr),
+        Expression::First(expr) => annotated_sexp("fst", &None, expr),
+        Expression::Second(expr) => annotated_sexp("snd", &None, expr),
+        Expression::Succ(expr) => annotated_sexp("succ", &None, expr),
+        Expression::Pred(expr) => annotated_sexp("pred", &None, expr),
+        Expression::IsZero(expr) => annotated_sexp("iszero", &None, expr),
+        Expression::Fix(expr) => annotated_sexp("fix", &None, expr),
+        Expression::Unit => "()".to_string(),
+        Expression::Zero => "0".to_string(),
+        Expression::True => "true".to_string(),
+        Expression::False => "false".to_string(),
+    }
+}
+
+/// The expression as a Graphviz graph with the given name, or as an S-expression. \
+/// In the graph, applications are `@` nodes, types are boxes, free variables are red,
+/// and every bound (type) variable has a dashed edge from the node that binds it
+pub(crate) fn dump_expression(expr: &Expression, format: DumpFormat, name: &str) -> String {
+    match format {
+        DumpFormat::Dot => {
+            let mut dot = new_dot();
+            dot.expression(expr);
+            dot.graph(name)
+        }
+        DumpFormat::Sexp => expression_sexp(expr),
+    }
+}
+
+/// The judgement as a Graphviz graph with the given name, or as an S-expression,
+/// e.g. `x : A ⊢ x : A` is `(⊢ ((x A)) x A)`. \
+/// In the graph, the variables of the context bind their occurrences like abstractions do
+pub(crate) fn dump_judgement(judgement: &Judgement, format: DumpFormat, name: &str) -> String {
+    match format {
+        DumpFormat::Dot => {
+            let mut dot = new_dot();
+            dot.judgement(judgement);
+            dot.graph(name)
+        }
+        DumpFormat::Sexp => {
+            let Judgement::Judgement(context, expr, typ) = judgement;
+            let context = context
+                .iter()
+                .map(|(var, typ)| format!("({} {})", var, type_sexp(typ)))
+                .collect::<Vec<String>>();
+            format!(
+                "(⊢ ({}) {} {})",
+                context.join(" "),
+                expression_sexp(expr),
+                type_sexp(typ)
+            )
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    dump::{dump_expression, dump_judgement, DumpFormat},
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::{is_judgement, parse, parse_expression},
+    tokenizer::tokenize,
+    utils::{exit, Failure},
+};
+
+/// Dump mode: print the tree of every judgement (or expression) in the file (or stdin) that the parser builds,
+/// as a Graphviz graph (`dot`, one `digraph` per line) or as an S-expression (`sexp`). \
+/// `--dump-ast=<dot|sexp> [file]` (or `--dump-ast <dot|sexp> [file]`)
+pub(super) fn dump_mode(args: Vec<String>) {
+    let mut args = args.into_iter().skip(1);
+    let format = match args.next().as_deref() {
+        Some("--dump-ast") => args.next(),
+        Some(arg) => arg.strip_prefix("--dump-ast=").map(str::to_string),
+        None => None,
+    };
+    let Some(format) = format.as_deref().and_then(DumpFormat::from_name) else {
+        eprintln!("--dump-ast expects dot or sexp!");
+        exit(Failure::Usage);
+    };
+
+    let lines = match args.next() {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+    dump_lines(lines, format);
+}
+
+/// Print the tree of every line in the format (see dump mode)
+pub(super) fn dump_lines(lines: Vec<(usize, String)>, format: DumpFormat) {
+    for (idx, line) in lines {
+        let tokens = tokenize(&line, idx);
+        let name = format!("line{}", idx + 1);
+        if is_judgement(&tokens) {
+            let judgement = parse(&tokens, idx);
+            println!("{}", dump_judgement(&judgement, format, &name));
+        } else {
+            let expression = parse_expression(&tokens, idx);
+            println!("{}", dump_expression(&expression, format, &name));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::{is_judgement, parse, parse_open_expression, Expression, Judgement},
+    reducer::{erase, reduce},
+    tokenizer::tokenize,
+    type_checker::{synthesise_type, type_check},
+    utils::mode_arguments,
+};
+
+/// Evaluation mode: typecheck every judgement (or annotated expression) in the file (or stdin),
+/// and print the normal form of its expression, e.g. `fst (a, b)` prints `a`. \
+/// With `--erase` the types are erased from the normal form, so it prints the untyped term. \
+/// `-n [--erase] [file]` (normal forms)
+pub(super) fn eval_mode(args: Vec<String>) {
+    let arguments = mode_arguments(
+        &args,
+        "Usage: lc -n [--erase] [FILE]\n\nPrint the normal form of every typed expression (or judgement) in FILE, or stdin if there is none\n\nOptions:\n  --erase  Erase the types from the normal form",
+        &["--erase"],
+        &[],
+    );
+    let erased = arguments.has("--erase");
+
+    let lines = match arguments.file {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+    evaluate_lines(lines, erased);
+}
+
+/// Typecheck every line and print its normal form, without types if `erased` (see evaluation mode)
+pub(super) fn evaluate_lines(lines: Vec<(usize, String)>, erased: bool) {
+    let expressions: Vec<Expression> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            // only well-typed expressions are evaluated, so they do not get stuck
+            let expression = if is_judgement(&tokens) {
+                let judgement = parse(&tokens, idx);
+                type_check(&judgement, idx);
+                let Judgement::Judgement(_, expression, _) = judgement;
+                *expression
+            } else {
+                let (context, expression) = parse_open_expression(&tokens, idx);
+                synthesise_type(&expression, &context, idx);
+                expression
+            };
+            let normal_form = reduce(expression, idx);
+            if erased {
+                erase(&normal_form)
+            } else {
+                normal_form
+            }
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the expressions
+    for expression in expressions {
+        println!("{}", expression);
+    }
+}
@@ -0,0 +1,50 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::parse,
+    printer::{pretty_judgement, PrintConfig},
+    tokenizer::tokenize,
+    utils::{exit, mode_arguments, Failure},
+};
+
+/// Format mode: print every judgement in the file (or stdin) with the given layout. \
+/// `-f [--ascii] [--space] [--width N] [file]`
+pub(super) fn format_mode(args: Vec<String>) {
+    let mut config = PrintConfig::default();
+    let arguments = mode_arguments(
+        &args,
+        "Usage: lc -f [--ascii] [--space] [--width N] [FILE]\n\nPretty-print every judgement in FILE, or stdin if there is none\n\nOptions:\n  --ascii      Print \\ and |- instead of λ and ⊢\n  --space      Separate the bound variable and the body with a space instead of a dot\n  --width <N>  Break the lines to fit in N columns",
+        &["--ascii", "--space"],
+        &["--width"],
+    );
+    for (option, value) in &arguments.options {
+        match (option.as_str(), value) {
+            ("--ascii", _) => config.unicode = false,
+            ("--space", _) => config.dot = false,
+            (_, value) => match value.as_deref().and_then(|width| width.parse().ok()) {
+                Some(width) => config.width = Some(width),
+                None => {
+                    eprintln!("--width expects a number!");
+                    exit(Failure::Usage);
+                }
+            },
+        }
+    }
+
+    let lines = match arguments.file {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+    format_lines(lines, &config);
+}
+
+/// Print every judgement with the layout of the config (see format mode)
+pub(super) fn format_lines(lines: Vec<(usize, String)>, config: &PrintConfig) {
+    for (idx, line) in lines {
+        let tokens = tokenize(&line, idx);
+        let judgement = parse(&tokens, idx);
+        println!("{}", pretty_judgement(&judgement, config));
+    }
+}
@@ -0,0 +1,47 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    inference::{infer, infer_judgement},
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::{is_judgement, parse, parse_expression},
+    tokenizer::tokenize,
+    utils::mode_arguments,
+};
+
+/// Inference mode: infer the principal type of every expression in the file (or stdin). \
+/// The bound variables do not need a type, e.g. `λf.λx.f x` prints `λf.λx.f x : (a -> b) -> a -> b`. \
+/// A judgement is derivable if its type is an instance of the principal type of its expression. \
+/// `-i [file]`
+pub(super) fn infer_mode(args: Vec<String>) {
+    let arguments = mode_arguments(
+        &args,
+        "Usage: lc -i [FILE]\n\nInfer the type of every expression (or judgement) in FILE, or stdin if there is none",
+        &[],
+        &[],
+    );
+    let lines = match arguments.file {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+
+    let results: Vec<String> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            if is_judgement(&tokens) {
+                let judgement = parse(&tokens, idx);
+                infer_judgement(&judgement, idx);
+                judgement.to_string()
+            } else {
+                let expression = parse_expression(&tokens, idx);
+                let typ = infer(&expression, idx);
+                format!("{} : {}", expression, typ)
+            }
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the results
+    for result in results {
+        println!("{}", result);
+    }
+}
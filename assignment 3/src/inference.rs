@@ -0,0 +1,511 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+// Import handy dbg! macro (shadowing std::dbg! macro)
+use crate::{
+    dbg,
+    parser::{Expression, Judgement, Type},
+    printer::unknown_name,
+    types::{alpha_equivalent, rebuild},
+    utils::{exit, Failure},
+};
+
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug)]
+enum InferError {
+    /// A variable that is not bound by any abstraction
+    UnknownType(String),
+    /// Two types that cannot be made equal
+    MismatchedTypes(Type, Type),
+    /// An unknown type that would have to contain itself, e.g. a = a -> b for λx.x x
+    InfiniteType(Type, Type),
+    /// A type abstraction or application, whose types are not inferred
+    TypeAbstraction(Expression),
+}
+
+impl Display for InferError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            InferError::UnknownType(t) => write!(f, "Unknown type of variable: {}", t),
+            InferError::MismatchedTypes(t1, t2) => {
+                write!(f, "Mismatched types, cannot unify {} with {}", t1, t2)
+            }
+            InferError::InfiniteType(t1, t2) => {
+                write!(f, "Infinite type, {} occurs in {}", t1, t2)
+            }
+            InferError::TypeAbstraction(expr) => {
+                write!(
+                    f,
+                    "Cannot infer the type of '{}', type abstractions and applications need the typechecker",
+                    expr
+                )
+            }
+        }
+    }
+}
+
+type InferResult<T> = std::result::Result<T, InferError>;
+
+/// A type scheme, a type that is polymorphic in some of its unknown types, e.g. ∀a. a -> a
+#[derive(Debug, Clone)]
+pub(crate) struct Scheme {
+    /// The quantified unknown types
+    vars: Vec<usize>,
+    typ: Type,
+}
+
+impl Scheme {
+    /// A type that is not polymorphic
+    fn monomorphic(typ: Type) -> Self {
+        Scheme {
+            vars: Vec::new(),
+            typ,
+        }
+    }
+}
+
+impl Display for Scheme {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if self.vars.is_empty() {
+            return write!(f, "{}", self.typ);
+        }
+        let vars = self
+            .vars
+            .iter()
+            .map(|n| unknown_name(*n))
+            .collect::<Vec<String>>();
+        write!(f, "∀{}. {}", vars.join(" "), self.typ)
+    }
+}
+
+/// The type schemes of the variables in scope, later bindings shadow earlier ones
+type Context = Vec<(String, Scheme)>;
+
+/// Hindley-Milner style type inference, unknown types are solved by unification. \
+/// Type variables from annotations (A, B, ...) are fixed types, they only unify with themselves.
+struct Inference {
+    /// The type each unknown type is bound to, if any (indexed by the number of the unknown)
+    bindings: Vec<Option<Type>>,
+}
+
+impl Inference {
+    fn new() -> Self {
+        Inference {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// A new unknown type
+    fn fresh(&mut self) -> Type {
+        self.bindings.push(None);
+        Type::Unknown(self.bindings.len() - 1)
+    }
+
+    /// Replace all bound unknown types in the type by what they are bound to
+    fn resolve(&self, typ: &Type) -> Type {
+        match typ {
+            Type::Unknown(n) => match &self.bindings[*n] {
+                Some(bound) => self.resolve(bound),
+                None => typ.clone(),
+            },
+            Type::Function(t1, t2) | Type::Product(t1, t2) | Type::Sum(t1, t2) => {
+                rebuild(typ, self.resolve(t1), self.resolve(t2))
+            }
+            Type::ForAll(var, body) => Type::ForAll(var.clone(), Box::new(self.resolve(body))),
+            Type::Variable(_) | Type::Unit | Type::Nat | Type::Bool | Type::Empty => typ.clone(),
+        }
+    }
+
+    /// Whether unknown type n occurs in the (resolved) type
+    fn occurs(&self, n: usize, typ: &Type) -> bool {
+        match typ {
+            Type::Unknown(m) => *m == n,
+            Type::Function(t1, t2) | Type::Product(t1, t2) | Type::Sum(t1, t2) => {
+                self.occurs(n, t1) || self.occurs(n, t2)
+            }
+            Type::ForAll(_, body) => self.occurs(n, body),
+            Type::Variable(_) | Type::Unit | Type::Nat | Type::Bool | Type::Empty => false,
+        }
+    }
+
+    /// Make both types equal by binding unknown types
+    fn unify(&mut self, t1: &Type, t2: &Type) -> InferResult<()> {
+        let t1 = self.resolve(t1);
+        let t2 = self.resolve(t2);
+        match (&t1, &t2) {
+            (Type::Unknown(n), Type::Unknown(m)) if n == m => Ok(()),
+            (Type::Unknown(n), typ) | (typ, Type::Unknown(n)) => {
+                // occurs check, a = a -> b has no (finite) solution
+                if self.occurs(*n, typ) {
+                    return Err(InferError::InfiniteType(Type::Unknown(*n), typ.clone()));
+                }
+                self.bindings[*n] = Some(typ.clone());
+                Ok(())
+            }
+            (Type::Function(a1, b1), Type::Function(a2, b2))
+            | (Type::Product(a1, b1), Type::Product(a2, b2))
+            | (Type::Sum(a1, b1), Type::Sum(a2, b2)) => {
+                self.unify(a1, a2)?;
+                self.unify(b1, b2)
+            }
+            (Type::Variable(a), Type::Variable(b)) if a == b => Ok(()),
+            (Type::Unit, Type::Unit)
+            | (Type::Nat, Type::Nat)
+            | (Type::Bool, Type::Bool)
+            | (Type::Empty, Type::Empty) => Ok(()),
+            // polymorphic types (from annotations) are only equal up to renaming
+            (Type::ForAll(_, _), Type::ForAll(_, _)) if alpha_equivalent(&t1, &t2) => Ok(()),
+            _ => Err(InferError::MismatchedTypes(t1, t2)),
+        }
+    }
+
+    /// Print the types in an error as far as they were solved, with unknown types a, b, c, ...
+    fn explain(&self, err: InferError) -> InferError {
+        let mut names = Vec::new();
+        match err {
+            InferError::MismatchedTypes(t1, t2) => {
+                let t1 = rename(&self.resolve(&t1), &mut names);
+                InferError::MismatchedTypes(t1, rename(&self.resolve(&t2), &mut names))
+            }
+            InferError::InfiniteType(t1, t2) => {
+                let t1 = rename(&self.resolve(&t1), &mut names);
+                InferError::InfiniteType(t1, rename(&self.resolve(&t2), &mut names))
+            }
+            err => err,
+        }
+    }
+
+    /// Replace the quantified unknown types of the scheme by new unknown types
+    fn instantiate(&mut self, scheme: &Scheme) -> Type {
+        let fresh = scheme
+            .vars
+            .iter()
+            .map(|n| (*n, self.fresh()))
+            .collect::<Vec<(usize, Type)>>();
+        substitute(&scheme.typ, &fresh)
+    }
+
+    /// Quantify the unknown types of the type that are not bound in the context
+    fn generalise(&self, typ: &Type, context: &Context) -> Scheme {
+        let mut in_context = Vec::new();
+        for (_, scheme) in context {
+            let mut vars = Vec::new();
+            unknowns(&self.resolve(&scheme.typ), &mut vars);
+            in_context.extend(vars.into_iter().filter(|n| !scheme.vars.contains(n)));
+        }
+
+        let typ = self.resolve(typ);
+        let mut vars = Vec::new();
+        unknowns(&typ, &mut vars);
+        vars.retain(|n| !in_context.contains(n));
+        Scheme { vars, typ }
+    }
+
+    /// Infer the type of the expression in the given context
+    fn infer(&mut self, expr: &Expression, context: &mut Context) -> InferResult<Type> {
+        match expr {
+            Expression::Variable(name) => match context.iter().rev().find(|(var, _)| var == name) {
+                Some((_, scheme)) => {
+                    let scheme = scheme.clone();
+                    Ok(self.instantiate(&scheme))
+                }
+                None => Err(InferError::UnknownType(name.clone())),
+            },
+            Expression::Abstraction(var, typ, body) => {
+                // an annotation fixes the type of the bound variable, otherwise it is unknown
+                let var_type = match typ {
+                    Some(typ) => *typ.clone(),
+                    None => self.fresh(),
+                };
+                context.push((var.clone(), Scheme::monomorphic(var_type.clone())));
+                let body_type = self.infer(body, context);
+                context.pop();
+                Ok(Type::Function(Box::new(var_type), Box::new(body_type?)))
+            }
+            Expression::Application(lexpr, rexpr) => {
+                let function_type = self.infer(lexpr, context)?;
+                let argument_type = self.infer(rexpr, context)?;
+                let result_type = self.fresh();
+                self.unify(
+                    &function_type,
+                    &Type::Function(Box::new(argument_type), Box::new(result_type.clone())),
+                )?;
+                Ok(result_type)
+            }
+            Expression::Let(var, bound, body) => {
+                // let-polymorphism: the bound variable can be used at a different type every time
+                let bound_type = self.infer(bound, context)?;
+                let scheme = self.generalise(&bound_type, context);
+                dbg!(&scheme);
+                context.push((var.clone(), scheme));
+                let body_type = self.infer(body, context);
+                context.pop();
+                body_type
+            }
+            Expression::TypeAbstraction(_, _) | Expression::TypeApplication(_, _) => {
+                Err(InferError::TypeAbstraction(expr.clone()))
+            }
+            Expression::Unit => Ok(Type::Unit),
+            Expression::Pair(left, right) => {
+                let left_type = self.infer(left, context)?;
+                let right_type = self.infer(right, context)?;
+                Ok(Type::Product(Box::new(left_type), Box::new(right_type)))
+            }
+            Expression::First(pair) | Expression::Second(pair) => {
+                let pair_type = self.infer(pair, context)?;
+                let (left_type, right_type) = (self.fresh(), self.fresh());
+                self.unify(
+                    &pair_type,
+                    &Type::Product(Box::new(left_type.clone()), Box::new(right_type.clone())),
+                )?;
+                match expr {
+                    Expression::First(_) => Ok(left_type),
+                    _ => Ok(right_type),
+                }
+            }
+            Expression::Left(typ, inner) | Expression::Right(typ, inner) => {
+                let inner_type = self.infer(inner, context)?;
+                // the other side of the sum is unknown, unless the injection has a type
+                let other_type = self.fresh();
+                let sum_type = match expr {
+                    Expression::Left(_, _) => Type::Sum(Box::new(inner_type), Box::new(other_type)),
+                    _ => Type::Sum(Box::new(other_type), Box::new(inner_type)),
+                };
+                if let Some(typ) = typ {
+                    self.unify(typ, &sum_type)?;
+                }
+                Ok(sum_type)
+            }
+            Expression::Case(scrutinee, left, left_branch, right, right_branch) => {
+                let scrutinee_type = self.infer(scrutinee, context)?;
+                let (left_type, right_type) = (self.fresh(), self.fresh());
+                self.unify(
+                    &scrutinee_type,
+                    &Type::Sum(Box::new(left_type.clone()), Box::new(right_type.clone())),
+                )?;
+
+                context.push((left.clone(), Scheme::monomorphic(left_type)));
+                let left_branch_type = self.infer(left_branch, context);
+                context.pop();
+                context.push((right.clone(), Scheme::monomorphic(right_type)));
+                let right_branch_type = self.infer(right_branch, context);
+                context.pop();
+
+                // both branches have the type of the whole case
+                let left_branch_type = left_branch_type?;
+                self.unify(&left_branch_type, &right_branch_type?)?;
+                Ok(left_branch_type)
+            }
+            Expression::Ascription(inner, typ) => {
+                // like an annotation, the ascribed type is fixed
+                let inner_type = self.infer(inner, context)?;
+                self.unify(&inner_type, typ)?;
+                Ok(*typ.clone())
+            }
+            Expression::Zero => Ok(Type::Nat),
+            Expression::Succ(inner) | Expression::Pred(inner) | Expression::IsZero(inner) => {
+                let inner_type = self.infer(inner, context)?;
+                self.unify(&inner_type, &Type::Nat)?;
+                match expr {
+                    Expression::IsZero(_) => Ok(Type::Bool),
+                    _ => Ok(Type::Nat),
+                }
+            }
+            Expression::True | Expression::False => Ok(Type::Bool),
+            Expression::If(condition, then_branch, else_branch) => {
+                let condition_type = self.infer(condition, context)?;
+                self.unify(&condition_type, &Type::Bool)?;
+                // both branches have the type of the whole if
+                let then_type = self.infer(then_branch, context)?;
+                let else_type = self.infer(else_branch, context)?;
+                self.unify(&then_type, &else_type)?;
+                Ok(then_type)
+            }
+            Expression::Fix(function) => {
+                // the function maps the type of its fixed point to itself
+                let function_type = self.infer(function, context)?;
+                let typ = self.fresh();
+                self.unify(
+                    &function_type,
+                    &Type::Function(Box::new(typ.clone()), Box::new(typ.clone())),
+                )?;
+                Ok(typ)
+            }
+            Expression::Absurd(typ, inner) => {
+                // from ⊥ anything follows, the conclusion is unknown unless absurd has a type
+                let inner_type = self.infer(inner, context)?;
+                self.unify(&inner_type, &Type::Empty)?;
+                match typ {
+                    Some(typ) => Ok(*typ.clone()),
+                    None => Ok(self.fresh()),
+                }
+            }
+        }
+    }
+}
+
+/// Collect the unknown types in the type
+fn unknowns(typ: &Type, vars: &mut Vec<usize>) {
+    match typ {
+        Type::Unknown(n) => {
+            if !vars.contains(n) {
+                vars.push(*n);
+            }
+        }
+        Type::Function(t1, t2) | Type::Product(t1, t2) | Type::Sum(t1, t2) => {
+            unknowns(t1, vars);
+            unknowns(t2, vars);
+        }
+        Type::ForAll(_, body) => unknowns(body, vars),
+        Type::Variable(_) | Type::Unit | Type::Nat | Type::Bool | Type::Empty => {}
+    }
+}
+
+/// Replace the unknown types in the type by the given types
+fn substitute(typ: &Type, substitution: &[(usize, Type)]) -> Type {
+    match typ {
+        Type::Unknown(n) => match substitution.iter().find(|(m, _)| m == n) {
+            Some((_, replacement)) => replacement.clone(),
+            None => typ.clone(),
+        },
+        Type::Function(t1, t2) | Type::Product(t1, t2) | Type::Sum(t1, t2) => rebuild(
+            typ,
+            substitute(t1, substitution),
+            substitute(t2, substitution),
+        ),
+        Type::ForAll(var, body) => {
+            Type::ForAll(var.clone(), Box::new(substitute(body, substitution)))
+        }
+        Type::Variable(_) | Type::Unit | Type::Nat | Type::Bool | Type::Empty => typ.clone(),
+    }
+}
+
+/// Renumber the unknown types in order of appearance, so they print as a, b, c, ...
+fn rename(typ: &Type, names: &mut Vec<usize>) -> Type {
+    match typ {
+        Type::Unknown(n) => match names.iter().position(|m| m == n) {
+            Some(pos) => Type::Unknown(pos),
+            None => {
+                names.push(*n);
+                Type::Unknown(names.len() - 1)
+            }
+        },
+        Type::Function(t1, t2) | Type::Product(t1, t2) | Type::Sum(t1, t2) => {
+            let t1 = rename(t1, names);
+            rebuild(typ, t1, rename(t2, names))
+        }
+        Type::ForAll(var, body) => Type::ForAll(var.clone(), Box::new(rename(body, names))),
+        Type::Variable(_) | Type::Unit | Type::Nat | Type::Bool | Type::Empty => typ.clone(),
+    }
+}
+
+/// Infer the principal type scheme of a closed expression
+fn _infer(expr: &Expression) -> InferResult<Scheme> {
+    let mut inference = Inference::new();
+    let typ = inference
+        .infer(expr, &mut Context::new())
+        .map_err(|err| inference.explain(err))?;
+    // the expression is closed, so it is polymorphic in all of its unknown types
+    let typ = rename(&inference.resolve(&typ), &mut Vec::new());
+    let mut vars = Vec::new();
+    unknowns(&typ, &mut vars);
+    let scheme = Scheme { vars, typ };
+    dbg!(&scheme);
+    Ok(scheme)
+}
+
+/// Check that the type of the judgement is an instance of the principal type of its expression
+fn _infer_judgement(judgement: &Judgement) -> InferResult<()> {
+    let Judgement::Judgement(context, expr, typ) = judgement;
+    let mut inference = Inference::new();
+    // the types in the context of the judgement are fixed, so not polymorphic
+    let mut context = context
+        .iter()
+        .map(|(var, typ)| (var.clone(), Scheme::monomorphic(typ.clone())))
+        .collect::<Context>();
+    let found = inference
+        .infer(expr, &mut context)
+        .map_err(|err| inference.explain(err))?;
+    let principal = rename(&inference.resolve(&found), &mut Vec::new());
+    match inference.unify(typ, &found) {
+        Ok(()) => Ok(()),
+        // report the whole types, not just the parts that did not unify
+        Err(InferError::MismatchedTypes(_, _)) => {
+            Err(InferError::MismatchedTypes(*typ.clone(), principal))
+        }
+        Err(err) => Err(inference.explain(err)),
+    }
+}
+
+/// Infer the principal type scheme of the expression
+/// If the expression has no type, prints an error and exits the program
+///
+/// # Arguments
+/// * `expr` - The expression to infer the type of
+/// * `idx` - The index of the line the expression is on (for error printing)
+///
+/// # Returns
+/// The principal type scheme, e.g. ∀a. a -> a
+///
+/// # Error
+/// "Invalid expression [{err_code}] caught during type inference on line {idx}!"
+pub(crate) fn infer(expr: &Expression, idx: usize) -> Scheme {
+    match _infer(expr) {
+        Ok(typ) => typ,
+        Err(err_code) => {
+            eprintln!(
+                "Invalid expression [{}] caught during type inference on line {}!",
+                err_code,
+                idx + 1
+            );
+            exit(Failure::Type);
+        }
+    }
+}
+
+/// Check the judgement with type inference,
+/// so the bound variables of its expression do not need a type. \
+/// If the judgement is not derivable, prints an error and exits the program
+pub(crate) fn infer_judgement(judgement: &Judgement, idx: usize) {
+    if let Err(err_code) = _infer_judgement(judgement) {
+        eprintln!(
+            "Invalid judgement [{}] caught during type inference on line {}!",
+            err_code,
+            idx + 1
+        );
+        exit(Failure::Type);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser::manual_parse_expression, tokenizer::manual_tokenize};
+
+    fn infer_str(input: &str) -> InferResult<Scheme> {
+        let tokens = manual_tokenize(input).unwrap();
+        _infer(&manual_parse_expression(&tokens).unwrap())
+    }
+
+    #[test]
+    fn self_application_is_an_infinite_type() {
+        assert!(matches!(
+            infer_str("λx.x x"),
+            Err(InferError::InfiniteType(_, _))
+        ));
+        assert!(matches!(
+            infer_str("\\x.x x"),
+            Err(InferError::InfiniteType(_, _))
+        ));
+    }
+
+    #[test]
+    fn abstraction_without_dot_ends_at_its_body() {
+        // `\x x x` is `(\x x) x`, so the last x is free, not an infinite type
+        assert!(matches!(
+            infer_str("\\x x x"),
+            Err(InferError::UnknownType(var)) if var == "x"
+        ));
+    }
+}
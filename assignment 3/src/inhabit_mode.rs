@@ -0,0 +1,45 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    inhabitation::inhabit,
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::{parse_type, Judgement},
+    printer::type_string,
+    tokenizer::tokenize,
+    utils::mode_arguments,
+};
+
+/// Proof search mode: for every type in the file (or stdin), search the smallest closed term of it,
+/// e.g. `A -> B -> A` prints `λx^A.λy^B.x : A -> B -> A`, and `((A -> B) -> A) -> A` prints that it is uninhabited. \
+/// `-p [file]`
+pub(super) fn inhabit_mode(args: Vec<String>) {
+    let arguments = mode_arguments(
+        &args,
+        "Usage: lc -p [FILE]\n\nPrint a term of every type in FILE, or stdin if there is none, or that it is uninhabited",
+        &[],
+        &[],
+    );
+    let lines = match arguments.file {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+
+    let results: Vec<String> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            let typ = parse_type(&tokens, idx);
+            match inhabit(&typ, idx) {
+                Some(term) => {
+                    Judgement::Judgement(Vec::new(), Box::new(term), Box::new(typ)).to_string()
+                }
+                None => format!("{} is uninhabited", type_string(&typ)),
+            }
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the results
+    for result in results {
+        println!("{}", result);
+    }
+}
@@ -0,0 +1,174 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::dbg;
+use crate::utils::{exit, Failure};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{
+    parser::{Context, Expression, Type},
+    printer::type_string,
+};
+
+#[derive(Debug)]
+enum InhabitError {
+    /// A type that is not built from type variables and arrows, so it is not implicational
+    NotImplicational(Type),
+}
+
+impl Display for InhabitError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            InhabitError::NotImplicational(typ) => write!(
+                f,
+                "Only types built from type variables and arrows can be searched, found {}",
+                typ
+            ),
+        }
+    }
+}
+
+type InhabitResult<T> = std::result::Result<T, InhabitError>;
+
+/// A goal of the search: the types in the context (as a set, the names do not matter)
+/// and the type variable to inhabit
+type Goal = (BTreeSet<String>, String);
+
+/// The names of the bound variables, `x`, `y`, `z`, ..., then `x1`, `y1`, ...
+fn variable_name(n: usize) -> String {
+    const NAMES: [&str; 6] = ["x", "y", "z", "u", "v", "w"];
+    match n / NAMES.len() {
+        0 => NAMES[n].to_string(),
+        round => format!("{}{}", NAMES[n % NAMES.len()], round),
+    }
+}
+
+/// Split `A1 -> ... -> An -> X` into its premises `A1, ..., An` and its target `X`
+fn split_arrows(typ: &Type) -> (Vec<&Type>, &String) {
+    let mut premises = Vec::new();
+    let mut typ = typ;
+    while let Type::Function(premise, conclusion) = typ {
+        premises.push(premise.as_ref());
+        typ = conclusion;
+    }
+    match typ {
+        Type::Variable(target) => (premises, target),
+        _ => unreachable!("implicational types end in a type variable"),
+    }
+}
+
+fn is_implicational(typ: &Type) -> bool {
+    match typ {
+        Type::Variable(_) => true,
+        Type::Function(premise, conclusion) => {
+            is_implicational(premise) && is_implicational(conclusion)
+        }
+        _ => false,
+    }
+}
+
+/// The number of nodes (variables, abstractions and applications) of the term
+fn size(expr: &Expression) -> usize {
+    match expr {
+        Expression::Abstraction(_, _, body) => 1 + size(body),
+        Expression::Application(lexpr, rexpr) => 1 + size(lexpr) + size(rexpr),
+        _ => 1,
+    }
+}
+
+/// Search the smallest β-normal η-long term of the type in the context:
+/// `λx1..xn. f t1 .. tm` where `x1..xn` bind the premises of the type,
+/// and `f : B1 -> .. -> Bm -> X` is a variable with the target `X` of the type,
+/// applied to terms `t1..tm` of `B1..Bm` (searched the same way). \
+/// A goal that already occurs on the path to it is not searched again (loop checking):
+/// the term below it could be put in place of the term above it, so the smallest term never repeats a goal.
+/// There are finitely many goals (the types in them are all subtypes of the original type),
+/// so the search always terminates, and None means the type is uninhabited.
+fn search(typ: &Type, context: &mut Context, path: &mut Vec<Goal>) -> Option<Expression> {
+    let (premises, target) = split_arrows(typ);
+    let bound = premises.len();
+    for premise in &premises {
+        context.push((variable_name(context.len()), (*premise).clone()));
+    }
+
+    let goal = (
+        context.iter().map(|(_, typ)| type_string(typ)).collect(),
+        target.clone(),
+    );
+    let mut best: Option<Expression> = None;
+    if !path.contains(&goal) {
+        path.push(goal);
+        // the variables with the target as their result, the most recently bound first
+        for (var, var_type) in context.clone().iter().rev() {
+            let (arguments, result) = split_arrows(var_type);
+            if result != target {
+                continue;
+            }
+            // the arguments are independent, so the smallest application has the smallest arguments
+            let mut application = Some(Expression::Variable(var.clone()));
+            for argument in arguments {
+                application = match (application, search(argument, context, path)) {
+                    (Some(lexpr), Some(rexpr)) => {
+                        Some(Expression::Application(Box::new(lexpr), Box::new(rexpr)))
+                    }
+                    _ => None,
+                };
+            }
+            if let Some(application) = application {
+                if best
+                    .as_ref()
+                    .is_none_or(|best| size(&application) < size(best))
+                {
+                    best = Some(application);
+                }
+            }
+        }
+        path.pop();
+    }
+
+    // bind the premises around the term, the innermost last
+    let bindings = context.split_off(context.len() - bound);
+    best.map(|body| {
+        bindings.into_iter().rev().fold(body, |body, (var, typ)| {
+            Expression::Abstraction(var, Some(Box::new(typ)), Box::new(body))
+        })
+    })
+}
+
+/// Returns the smallest closed term of the type, None if it is uninhabited,
+/// Err(InhabitError) if the type is not implicational.
+fn _inhabit(typ: &Type) -> InhabitResult<Option<Expression>> {
+    if !is_implicational(typ) {
+        return Err(InhabitError::NotImplicational(typ.clone()));
+    }
+    let term = search(typ, &mut Context::new(), &mut Vec::new());
+    dbg!(&term);
+    Ok(term)
+}
+
+/// Search the smallest closed β-normal η-long term of the type (a proof of it in intuitionistic implicational logic)
+/// If the type is not implicational, prints an error and exits the program
+///
+/// # Arguments
+/// * `typ` - The type to inhabit
+/// * `idx` - The index of the line the type is on (for error printing)
+///
+/// # Returns
+/// The term, None if the type is uninhabited (like Peirce's law `((A -> B) -> A) -> A`)
+///
+/// # Error
+/// "Invalid type [{err_code}] caught during searching on line {idx}!"
+pub(crate) fn inhabit(typ: &Type, idx: usize) -> Option<Expression> {
+    match _inhabit(typ) {
+        Ok(term) => term,
+        Err(err_code) => {
+            eprintln!(
+                "Invalid type [{}] caught during searching on line {}!",
+                err_code,
+                idx + 1
+            );
+            exit(Failure::Type);
+        }
+    }
+}
@@ -0,0 +1,376 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    parser::{Context, Expression, Type},
+    tokenizer::Token,
+    utils::Failure,
+};
+
+use std::fmt::{Display, Formatter, Result};
+
+/// A JSON value, written by hand so no external crates are needed. \
+/// The fields of an object keep their order, so the output is stable
+#[derive(Debug, Clone)]
+pub(crate) enum Json {
+    Null,
+    Number(usize),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl From<&str> for Json {
+    fn from(string: &str) -> Self {
+        Json::String(string.to_string())
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(option: Option<T>) -> Self {
+        option.map_or(Json::Null, Into::into)
+    }
+}
+
+/// Write the string quoted, escaping quotes, backslashes and control characters
+fn write_string(f: &mut Formatter, string: &str) -> Result {
+    write!(f, "\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Display the value as compact JSON on a single line
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Number(n) => write!(f, "{}", n),
+            Json::String(string) => write_string(f, string),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// An object with its kind first, e.g. `{"kind":"variable","name":"x"}`
+fn node(kind: &str, mut fields: Vec<(&'static str, Json)>) -> Json {
+    fields.insert(0, ("kind", kind.into()));
+    Json::Object(fields)
+}
+
+/// A token as `{"kind": ...}`, with its `value` if it has one (a variable name or a numeral)
+pub(crate) fn token_json(token: &Token) -> Json {
+    let kind = match token {
+        Token::Lambda(var) => return node("lambda", vec![("value", var.as_str().into())]),
+        Token::TypeLambda(var) => return node("type_lambda", vec![("value", var.as_str().into())]),
+        Token::LVariable(var) => return node("lvariable", vec![("value", var.as_str().into())]),
+        Token::UVariable(var) => return node("uvariable", vec![("value", var.as_str().into())]),
+        Token::Numeral(n) => return node("numeral", vec![("value", Json::Number(*n))]),
+        Token::Forall => "forall",
+        Token::LBracket => "lbracket",
+        Token::RBracket => "rbracket",
+        Token::LParen => "lparen",
+        Token::RParen => "rparen",
+        Token::Arrow => "arrow",
+        Token::Hat => "hat",
+        Token::Colon => "colon",
+        Token::Dot => "dot",
+        Token::Let => "let",
+        Token::In => "in",
+        Token::Equals => "equals",
+        Token::Turnstile => "turnstile",
+        Token::Comma => "comma",
+        Token::Star => "star",
+        Token::Plus => "plus",
+        Token::Bottom => "bottom",
+        Token::Not => "not",
+        Token::Fst => "fst",
+        Token::Snd => "snd",
+        Token::Inl => "inl",
+        Token::Inr => "inr",
+        Token::Case => "case",
+        Token::Of => "of",
+        Token::Bar => "bar",
+        Token::FatArrow => "fat_arrow",
+        Token::Square => "square",
+        Token::Succ => "succ",
+        Token::Pred => "pred",
+        Token::IsZero => "iszero",
+        Token::True => "true",
+        Token::False => "false",
+        Token::If => "if",
+        Token::Then => "then",
+        Token::Else => "else",
+        Token::Fix => "fix",
+        Token::Absurd => "absurd",
+    };
+    node(kind, Vec::new())
+}
+
+/// A type as nested objects, e.g. `A -> B` is
+/// `{"kind":"function","domain":{"kind":"variable","name":"A"},"codomain":{...}}`
+pub(crate) fn type_json(typ: &Type) -> Json {
+    match typ {
+        Type::Function(domain, codomain) => node(
+            "function",
+            vec![
+                ("domain", type_json(domain)),
+                ("codomain", type_json(codomain)),
+            ],
+        ),
+        Type::Variable(var) => node("variable", vec![("name", var.as_str().into())]),
+        Type::Unknown(n) => node("unknown", vec![("index", Json::Number(*n))]),
+        Type::ForAll(var, body) => node(
+            "forall",
+            vec![("variable", var.as_str().into()), ("body", type_json(body))],
+        ),
+        Type::Product(left, right) => node(
+            "product",
+            vec![("left", type_json(left)), ("right", type_json(right))],
+        ),
+        Type::Sum(left, right) => node(
+            "sum",
+            vec![("left", type_json(left)), ("right", type_json(right))],
+        ),
+        Type::Unit => node("unit", Vec::new()),
+        Type::Nat => node("nat", Vec::new()),
+        Type::Bool => node("bool", Vec::new()),
+        Type::Empty => node("empty", Vec::new()),
+    }
+}
+
+/// An optional annotation, `null` if there is none
+fn annotation_json(typ: &Option<Box<Type>>) -> Json {
+    typ.as_deref().map_or(Json::Null, type_json)
+}
+
+/// An expression as nested objects, e.g. `λx^A.x` is
+/// `{"kind":"abstraction","variable":"x","type":{...},"body":{"kind":"variable","name":"x"}}`
+pub(crate) fn expression_json(expr: &Expression) -> Json {
+    let json = |expr: &Expression| expression_json(expr);
+    match expr {
+        Expression::Application(function, argument) => node(
+            "application",
+            vec![("function", json(function)), ("argument", json(argument))],
+        ),
+        Expression::Variable(var) => node("variable", vec![("name", var.as_str().into())]),
+        Expression::Abstraction(var, typ, body) => node(
+            "abstraction",
+            vec![
+                ("variable", var.as_str().into()),
+                ("type", annotation_json(typ)),
+                ("body", json(body)),
+            ],
+        ),
+        Expression::Let(var, value, body) => node(
+            "let",
+            vec![
+                ("variable", var.as_str().into()),
+                ("value", json(value)),
+                ("body", json(body)),
+            ],
+        ),
+        Expression::TypeAbstraction(var, body) => node(
+            "type_abstraction",
+            vec![("variable", var.as_str().into()), ("body", json(body))],
+        ),
+        Expression::TypeApplication(expr, typ) => node(
+            "type_application",
+            vec![("expression", json(expr)), ("type", type_json(typ))],
+        ),
+        Expression::Unit => node("unit", Vec::new()),
+        Expression::Pair(left, right) => {
+            node("pair", vec![("left", json(left)), ("right", json(right))])
+        }
+        Expression::First(expr) => node("first", vec![("expression", json(expr))]),
+        Expression::Second(expr) => node("second", vec![("expression", json(expr))]),
+        Expression::Left(typ, expr) => node(
+            "left",
+            vec![("type", annotation_json(typ)), ("expression", json(expr))],
+        ),
+        Expression::Right(typ, expr) => node(
+            "right",
+            vec![("type", annotation_json(typ)), ("expression", json(expr))],
+        ),
+        Expression::Case(expr, left_var, left_body, right_var, right_body) => node(
+            "case",
+            vec![
+                ("expression", json(expr)),
+                ("left_variable", left_var.as_str().into()),
+                ("left_body", json(left_body)),
+                ("right_variable", right_var.as_str().into()),
+                ("right_body", json(right_body)),
+            ],
+        ),
+        Expression::Ascription(expr, typ) => node(
+            "ascription",
+            vec![("expression", json(expr)), ("type", type_json(typ))],
+        ),
+        Expression::Zero => node("zero", Vec::new()),
+        Expression::Succ(expr) => node("succ", vec![("expression", json(expr))]),
+        Expression::Pred(expr) => node("pred", vec![("expression", json(expr))]),
+        Expression::IsZero(expr) => node("iszero", vec![("expression", json(expr))]),
+        Expression::True => node("true", Vec::new()),
+        Expression::False => node("false", Vec::new()),
+        Expression::If(condition, then, otherwise) => node(
+            "if",
+            vec![
+                ("condition", json(condition)),
+                ("then", json(then)),
+                ("else", json(otherwise)),
+            ],
+        ),
+        Expression::Fix(expr) => node("fix", vec![("expression", json(expr))]),
+        Expression::Absurd(typ, expr) => node(
+            "absurd",
+            vec![("type", annotation_json(typ)), ("expression", json(expr))],
+        ),
+    }
+}
+
+/// The context as an array of `{"name": ..., "type": ...}`, in order
+pub(crate) fn context_json(context: &Context) -> Json {
+    Json::Array(
+        context
+            .iter()
+            .map(|(var, typ)| {
+                Json::Object(vec![
+                    ("name", var.as_str().into()),
+                    ("type", type_json(typ)),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// An error of one of the phases, as it is reported in the JSON output
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    /// The phase the error was caught in, which is also the exit code it gives
+    pub(crate) failure: Failure,
+    /// A stable identifier of the kind of error, e.g. `unclosed_lparen`
+    pub(crate) code: &'static str,
+    /// The message the text output prints between the brackets
+    pub(crate) message: String,
+    /// The (1-based) line the error is on, 0 if the input could not be read at all
+    pub(crate) line: usize,
+    /// The (1-based) column of the input the error is at, if it is known:
+    /// always for tokenizing errors, and for parsing errors at a single parenthesis or bracket
+    pub(crate) column: Option<usize>,
+    /// The part of the expression a type error is at, as the text output underlines it
+    pub(crate) underline: Option<Underline>,
+}
+
+/// The expression as the text output prints it, with the part that is underlined. \
+/// The printed expression can differ from the input (e.g. in spacing or line breaks),
+/// so the column is in the printed text and not in the input
+#[derive(Debug)]
+pub(crate) struct Underline {
+    pub(crate) text: String,
+    /// The (1-based) column in the text the underlined part starts at
+    pub(crate) column: usize,
+    pub(crate) length: usize,
+}
+
+impl Diagnostic {
+    fn json(&self) -> Json {
+        let phase = match self.failure {
+            Failure::Lex => "lex",
+            Failure::Parse => "parse",
+            Failure::Type => "type",
+            Failure::Reduce => "reduce",
+            Failure::Usage | Failure::Input => "input",
+        };
+        Json::Object(vec![
+            ("phase", phase.into()),
+            ("code", self.code.into()),
+            ("message", self.message.as_str().into()),
+            (
+                "span",
+                Json::Object(vec![
+                    ("line", Json::Number(self.line)),
+                    ("column", self.column.map(Json::Number).into()),
+                ]),
+            ),
+            (
+                "underline",
+                self.underline
+                    .as_ref()
+                    .map(|underline| {
+                        Json::Object(vec![
+                            ("text", underline.text.as_str().into()),
+                            ("column", Json::Number(underline.column)),
+                            ("length", Json::Number(underline.length)),
+                        ])
+                    })
+                    .into(),
+            ),
+        ])
+    }
+}
+
+/// Everything the JSON output reports about one input, the fields that were not reached are `null`
+#[derive(Debug, Default)]
+pub(crate) struct Report {
+    /// The file path, `<stdin>`, or `-e#n` for the expression of the n-th `-e`
+    pub(crate) source: String,
+    /// The (1-based) line the input starts on, 0 if the source could not be read
+    pub(crate) line: usize,
+    pub(crate) input: String,
+    pub(crate) tokens: Option<Json>,
+    pub(crate) context: Option<Json>,
+    pub(crate) expression: Option<Json>,
+    pub(crate) typ: Option<Json>,
+    pub(crate) normal_form: Option<Json>,
+    pub(crate) steps: Option<usize>,
+    pub(crate) output: Option<String>,
+    pub(crate) error: Option<Diagnostic>,
+}
+
+impl Report {
+    /// The report as one JSON object, with the fields in the documented order
+    pub(crate) fn json(&self) -> Json {
+        Json::Object(vec![
+            ("source", self.source.as_str().into()),
+            ("line", Json::Number(self.line)),
+            ("input", self.input.as_str().into()),
+            ("tokens", self.tokens.clone().into()),
+            ("context", self.context.clone().into()),
+            ("expression", self.expression.clone().into()),
+            ("type", self.typ.clone().into()),
+            ("normal_form", self.normal_form.clone().into()),
+            ("steps", self.steps.map(Json::Number).into()),
+            ("output", self.output.as_deref().into()),
+            ("error", self.error.as_ref().map(Diagnostic::json).into()),
+        ])
+    }
+}
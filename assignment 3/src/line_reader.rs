@@ -3,15 +3,48 @@
 
 // Import handy dbg! macro (shadowing std::dbg! macro)
 use crate::dbg;
+use crate::tokenizer::{is_blank, is_incomplete};
+use crate::utils::{exit, Failure};
 
 use std::{
     fs::File,
     io::{stdin, BufRead, BufReader},
 };
 
+/// Group physical lines into expressions, paired with the index of the line they start on. \
+/// Blank and comment-only lines are skipped, and an unfinished expression
+/// (e.g. unbalanced parentheses) is joined with the lines that follow it
+fn logical_lines(lines: Vec<String>) -> Vec<(usize, String)> {
+    let mut logical = Vec::new();
+    let mut pending = String::new();
+    let mut start = 0;
+
+    for (idx, line) in lines.into_iter().enumerate() {
+        if pending.is_empty() {
+            start = idx;
+        } else {
+            pending.push('\n');
+        }
+        pending.push_str(&line);
+
+        if is_incomplete(&pending) {
+            continue;
+        }
+        if !is_blank(&pending) {
+            logical.push((start, pending));
+        }
+        pending = String::new();
+    }
+    // the input ended while the expression was unfinished, the tokenizer or parser reports it
+    if !pending.is_empty() {
+        logical.push((start, pending));
+    }
+    logical
+}
+
 /// Read lines from file
 /// Standard mode: read until EOF
-pub(super) fn read_lines_from_file(filename: &str) -> Vec<String> {
+pub(super) fn read_lines_from_file(filename: &str) -> Vec<(usize, String)> {
     // get os args input
 
     let mut lines = Vec::new();
@@ -19,7 +52,7 @@ pub(super) fn read_lines_from_file(filename: &str) -> Vec<String> {
         Ok(file) => file,
         Err(error) => {
             eprintln!("Error opening file: {}", error);
-            std::process::exit(1);
+            exit(Failure::Input);
         }
     };
 
@@ -27,14 +60,42 @@ pub(super) fn read_lines_from_file(filename: &str) -> Vec<String> {
     for line in reader.lines() {
         lines.push(line.expect("Could not read line"));
     }
+    let lines = logical_lines(lines);
 
     dbg!(&lines);
     lines
 }
 
+/// Read lines from file, returns the error if the file cannot be read instead of exiting. \
+/// Only used for the JSON output, where the other sources are still reported
+pub(super) fn json_read_lines_from_file(filename: &str) -> std::io::Result<Vec<(usize, String)>> {
+    let reader = BufReader::new(File::open(filename)?);
+    let lines = reader.lines().collect::<std::io::Result<Vec<String>>>()?;
+    let lines = logical_lines(lines);
+    dbg!(&lines);
+    Ok(lines)
+}
+
+/// Read lines from terminal until EOF, returns the error if stdin cannot be read instead of exiting. \
+/// Only used for the JSON output
+pub(super) fn json_read_lines_from_terminal() -> std::io::Result<Vec<(usize, String)>> {
+    let lines = stdin()
+        .lock()
+        .lines()
+        .collect::<std::io::Result<Vec<String>>>()?;
+    Ok(logical_lines(lines))
+}
+
+/// Read lines from a string, e.g. an expression given on the command line
+pub(super) fn read_lines_from_string(input: &str) -> Vec<(usize, String)> {
+    let lines = logical_lines(input.lines().map(String::from).collect());
+    dbg!(&lines);
+    lines
+}
+
 /// Read lines from terminal
 /// Standard mode: read until EOF
-pub(super) fn read_lines_from_terminal() -> Vec<String> {
+pub(super) fn read_lines_from_terminal() -> Vec<(usize, String)> {
     // vec for storing lines
     let mut lines = Vec::new();
 
@@ -47,29 +108,30 @@ pub(super) fn read_lines_from_terminal() -> Vec<String> {
             }
             Err(error) => {
                 eprintln!("Error reading line: {}", error);
-                std::process::exit(1);
+                exit(Failure::Input);
             }
         }
     }
-    lines
+    logical_lines(lines)
 }
 
 /// Read line from terminal
-/// REPL mode: read until newline
-pub(super) fn read_line_from_terminal() -> String {
+/// REPL mode: read until newline, returns None at the end of the input
+pub(super) fn read_line_from_terminal() -> Option<String> {
     let mut input = String::new();
     match stdin().lock().read_line(&mut input) {
+        Ok(0) => None,
         Ok(_) => {
             let input = input.trim().to_string();
             dbg!(&input);
             if input == "quit" || input == "exit" {
                 std::process::exit(0);
             }
-            input
+            Some(input)
         }
         Err(error) => {
             eprintln!("Error reading line: {}", error);
-            std::process::exit(1);
+            exit(Failure::Input);
         }
     }
 }
@@ -8,12 +8,13 @@ mod tokenizer;
 use tokenizer::tokenize;
 
 mod parser;
-use parser::{parse, Judgement};
+use parser::{parse, set_left_assoc_arrows, Judgement};
 
 mod type_checker;
 use type_checker::type_check;
 
 mod utils;
+use utils::{exit, Failure};
 
 mod bench;
 use bench::bench;
@@ -21,13 +22,71 @@ use bench::bench;
 mod manual_mode;
 use manual_mode::manual_mode;
 
+mod printer;
+
+mod format_mode;
+use format_mode::format_mode;
+
+mod types;
+
+mod inference;
+
+mod infer_mode;
+use infer_mode::infer_mode;
+
+mod synth_mode;
+use synth_mode::synth_mode;
+
+mod derivation;
+
+mod reducer;
+
+mod eval_mode;
+use eval_mode::eval_mode;
+
+mod subject_reduction_mode;
+use subject_reduction_mode::subject_reduction_mode;
+
+mod inhabitation;
+
+mod inhabit_mode;
+use inhabit_mode::inhabit_mode;
+
+mod coc;
+
+mod coc_checker;
+
+mod coc_mode;
+use coc_mode::coc_mode;
+
+mod derivation_mode;
+use derivation_mode::derivation_mode;
+
+mod tactics;
+
+mod json;
+
+mod dump;
+mod dump_mode;
+use dump_mode::dump_mode;
+
+mod cli;
+use cli::cli;
+
+use std::path::Path;
+
 fn main() {
-    // if args provided
-    let lines: Vec<String>;
-    if std::env::args().len() > 1 {
-        // get args
-        let args = std::env::args().collect::<Vec<String>>();
+    let mut args = std::env::args().collect::<Vec<String>>();
+
+    // migration flag, parse `A -> B -> C` as `(A -> B) -> C` like before (works in every mode)
+    if let Some(pos) = args.iter().position(|arg| arg == "--left-assoc-arrows") {
+        args.remove(pos);
+        set_left_assoc_arrows(true);
+    }
 
+    // if args provided
+    let lines: Vec<(usize, String)>;
+    if args.len() > 1 {
         match args[1].as_str() {
             "-m" => {
                 manual_mode();
@@ -37,13 +96,61 @@ fn main() {
                 bench(args);
                 return;
             }
-            arg => {
+            "-f" => {
+                format_mode(args);
+                return;
+            }
+            "-i" => {
+                infer_mode(args);
+                return;
+            }
+            "-s" => {
+                synth_mode(args);
+                return;
+            }
+            "-d" => {
+                derivation_mode(args);
+                return;
+            }
+            "-n" => {
+                eval_mode(args);
+                return;
+            }
+            "-r" => {
+                subject_reduction_mode(args);
+                return;
+            }
+            "-p" => {
+                inhabit_mode(args);
+                return;
+            }
+            "-c" => {
+                coc_mode(args);
+                return;
+            }
+            arg if arg.starts_with("--dump-ast") => {
+                dump_mode(args);
+                return;
+            }
+            // the subcommands of `lc`, with their own options (see cli.rs)
+            "parse" | "reduce" | "typecheck" | "repl" | "bench" | "fmt" | "-h" | "--help"
+            | "-V" | "--version" | "-e" | "--expr" => {
+                cli(args);
+                return;
+            }
+            arg if Path::new(arg).exists() => {
                 lines = read_lines_from_file(arg);
             }
+            // neither a mode nor a file, so an unknown command (a usage error)
+            _ => {
+                cli(args);
+                return;
+            }
         }
 
         if lines.is_empty() {
-            panic!("Empty file!");
+            eprintln!("Empty file: {}!", args[1]);
+            exit(Failure::Input);
         }
     } else {
         // read input from terminal
@@ -52,7 +159,6 @@ fn main() {
 
     let judgements: Vec<Judgement> = lines
         .into_iter()
-        .enumerate()
         .map(|(idx, line)| {
             let tokens = tokenize(&line, idx);
             let judgement = parse(&tokens, idx);
@@ -72,7 +178,7 @@ fn main() {
                     judgement,
                     idx + 1
                 );
-                std::process::exit(1);
+                exit(Failure::Parse);
             }
 
             // type check the judgement
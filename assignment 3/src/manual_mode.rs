@@ -4,17 +4,34 @@
 use std::io::{self, Write};
 
 use crate::{
-    line_reader::read_line_from_terminal, parser::manual_parse, tokenizer::manual_tokenize,
+    line_reader::read_line_from_terminal,
+    parser::{manual_parse, manual_parse_expression, manual_parse_type, Type},
+    tactics::{ProofState, Tactic},
+    tokenizer::{is_blank, manual_tokenize},
     type_checker::manual_type_check,
 };
 
 pub(super) fn manual_mode() {
     println!("Manual mode activated!");
-    println!("Enter an expression to parse it.");
+    println!("Enter an expression to parse it, or `:prove <type>` to construct a term of the type with tactics.");
     loop {
         print!("Expression: ");
         io::stdout().flush().unwrap();
-        let input = read_line_from_terminal();
+        // the end of the input ends manual mode, like `quit`
+        let Some(input) = read_line_from_terminal() else {
+            println!();
+            return;
+        };
+        // nothing to parse on blank or comment-only input
+        if is_blank(&input) {
+            continue;
+        }
+        if let Some(goal) = input.strip_prefix(":prove") {
+            if let Some(typ) = manual_tokenize(goal).and_then(|tokens| manual_parse_type(&tokens)) {
+                proof_mode(typ);
+            }
+            continue;
+        }
         let tokens = manual_tokenize(&input);
         if tokens.is_none() {
             continue;
@@ -44,3 +61,86 @@ pub(super) fn manual_mode() {
         }
     }
 }
+
+/// Parse a tactic, e.g. `intro x` or `apply f`. \
+/// If the tactic (or its term) is invalid, prints an error and returns None
+fn manual_parse_tactic(input: &str) -> Option<Tactic> {
+    let (name, argument) = input.split_once(' ').unwrap_or((input, ""));
+    let argument = argument.trim();
+    let term = || manual_tokenize(argument).and_then(|tokens| manual_parse_expression(&tokens));
+    match (name, argument) {
+        ("intro", var) if var.chars().next().is_some_and(|c| c.is_ascii_lowercase()) => {
+            if var.chars().all(char::is_alphanumeric) {
+                Some(Tactic::Intro(var.to_string()))
+            } else {
+                eprintln!(
+                    "Invalid tactic [Invalid variable name: {}] caught during proving!",
+                    var
+                );
+                None
+            }
+        }
+        ("exact", _) => term().map(Tactic::Exact),
+        ("apply", _) => term().map(Tactic::Apply),
+        ("split", "") => Some(Tactic::Split),
+        ("left", "") => Some(Tactic::Left),
+        ("right", "") => Some(Tactic::Right),
+        _ => {
+            eprintln!(
+                "Invalid tactic [Unknown tactic: {}] caught during proving! (intro x, exact e, apply f, split, left, right, undo or abort)",
+                input
+            );
+            None
+        }
+    }
+}
+
+/// Proof mode: construct a term of the type by applying tactics to a stack of goals,
+/// printing the goals after every tactic, and finally the judgement of the constructed term. \
+/// `undo` takes back the last tactic, `abort` returns to manual mode
+fn proof_mode(typ: Type) {
+    let mut state = ProofState::new(typ);
+    let mut history: Vec<ProofState> = Vec::new();
+    println!("{}", state);
+    while !state.is_complete() {
+        print!("Tactic: ");
+        io::stdout().flush().unwrap();
+        // the end of the input aborts the proof, like `abort`
+        let Some(input) = read_line_from_terminal() else {
+            println!();
+            println!("Proof aborted");
+            return;
+        };
+        if is_blank(&input) {
+            continue;
+        }
+        match input.as_str() {
+            "abort" => {
+                println!("Proof aborted");
+                return;
+            }
+            "undo" => match history.pop() {
+                Some(previous) => state = previous,
+                None => println!("Nothing to undo"),
+            },
+            _ => {
+                let Some(tactic) = manual_parse_tactic(&input) else {
+                    continue;
+                };
+                let previous = state.clone();
+                if let Err(err_code) = state.apply(&tactic) {
+                    eprintln!("Invalid tactic [{}] caught during proving!", err_code);
+                    continue;
+                }
+                history.push(previous);
+            }
+        }
+        println!("Term: {}", state.term());
+        println!("{}", state);
+    }
+    // the constructed term has to convince the typechecker on its own as well
+    let judgement = state.judgement();
+    if manual_type_check(&judgement) {
+        println!("> {}", judgement);
+    }
+}
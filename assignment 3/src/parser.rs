@@ -3,9 +3,13 @@
 
 // Import handy dbg! macro (shadowing std::dbg! macro)
 use crate::dbg;
-use crate::tokenizer::Token;
+use crate::json::Diagnostic;
+use crate::printer::{pretty, pretty_judgement, type_string, PrintConfig};
+use crate::tokenizer::{Position, Token};
+use crate::utils::{exit, Failure};
 
 use std::fmt::{Display, Formatter, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 // Boxes are heap allocated, so we can use them to store the expression tree
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -14,8 +18,54 @@ pub(crate) enum Expression {
     Application(Box<Expression>, Box<Expression>),
     /// <Variable>
     Variable(String),
-    /// \ <Variable>^<Type> . <Expression>
-    Abstraction(String, Box<Type>, Box<Expression>),
+    /// \ <Variable>^<Type> . <Expression>, or \ <Variable> . <Expression> without a type
+    Abstraction(String, Option<Box<Type>>, Box<Expression>),
+    /// let <Variable> = <Expression> in <Expression>
+    Let(String, Box<Expression>, Box<Expression>),
+    /// Λ <TypeVariable> . <Expression>
+    TypeAbstraction(String, Box<Expression>),
+    /// <Expression> [<Type>]
+    TypeApplication(Box<Expression>, Box<Type>),
+    /// ()
+    Unit,
+    /// (<Expression>, <Expression>)
+    Pair(Box<Expression>, Box<Expression>),
+    /// fst <Expression>
+    First(Box<Expression>),
+    /// snd <Expression>
+    Second(Box<Expression>),
+    /// inl^<Type> <Expression>, where the type of the sum is optional
+    Left(Option<Box<Type>>, Box<Expression>),
+    /// inr^<Type> <Expression>, where the type of the sum is optional
+    Right(Option<Box<Type>>, Box<Expression>),
+    /// case <Expression> of inl <Variable> => <Expression> | inr <Variable> => <Expression>
+    Case(
+        Box<Expression>,
+        String,
+        Box<Expression>,
+        String,
+        Box<Expression>,
+    ),
+    /// (<Expression> : <Type>)
+    Ascription(Box<Expression>, Box<Type>),
+    /// 0, numerals like 3 are short for succ (succ (succ 0))
+    Zero,
+    /// succ <Expression>
+    Succ(Box<Expression>),
+    /// pred <Expression>
+    Pred(Box<Expression>),
+    /// iszero <Expression>
+    IsZero(Box<Expression>),
+    /// true
+    True,
+    /// false
+    False,
+    /// if <Expression> then <Expression> else <Expression>
+    If(Box<Expression>, Box<Expression>, Box<Expression>),
+    /// fix <Expression>
+    Fix(Box<Expression>),
+    /// absurd^<Type> <Expression>, where the type is optional
+    Absurd(Option<Box<Type>>, Box<Expression>),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -24,12 +74,31 @@ pub(crate) enum Type {
     Function(Box<Type>, Box<Type>),
     /// <Variable>
     Variable(String),
+    /// An unknown type, i.e. a type variable of type inference (printed as a, b, c, ...)
+    Unknown(usize),
+    /// ∀ <Variable> . <Type>
+    ForAll(String, Box<Type>),
+    /// <Type> * <Type>
+    Product(Box<Type>, Box<Type>),
+    /// <Type> + <Type>
+    Sum(Box<Type>, Box<Type>),
+    /// Unit
+    Unit,
+    /// Nat, the natural numbers
+    Nat,
+    /// Bool, the booleans
+    Bool,
+    /// ⊥ or Empty, the type without values (falsity)
+    Empty,
 }
 
+/// The types of the free variables of an expression, later bindings shadow earlier ones
+pub(crate) type Context = Vec<(String, Type)>;
+
 #[derive(Debug, Clone)]
 pub(crate) enum Judgement {
-    /// <Expression> : <Type>
-    Judgement(Box<Expression>, Box<Type>),
+    /// <Context> ⊢ <Expression> : <Type>, where the context and turnstile are optional
+    Judgement(Context, Box<Expression>, Box<Type>),
 }
 
 #[derive(Debug)]
@@ -39,7 +108,6 @@ enum ParseError {
     UnexpectedRParen,
     UnclosedLParen,
     NoAbstractionBody,
-    NoTypeHat,
     InvalidType,
     ArrowBeforeType,
     NoType,
@@ -48,6 +116,19 @@ enum ParseError {
     JudgementTooShort,
     EmptyJudgement,
     ExprSyntaxOutsideExpr,
+    InvalidLet,
+    MissingIn,
+    InvalidContext,
+    TooManyTurnstiles,
+    InvalidForall,
+    InvalidTypeApplication,
+    UnclosedLBracket,
+    UnexpectedRBracket,
+    InvalidCase,
+    MissingOf,
+    MissingOperand,
+    InvalidPair,
+    InvalidIf,
 }
 
 impl Display for ParseError {
@@ -58,7 +139,6 @@ impl Display for ParseError {
             ParseError::UnexpectedRParen => write!(f, "Unexpected right parenthesis"),
             ParseError::UnclosedLParen => write!(f, "Unclosed left parenthesis"),
             ParseError::NoAbstractionBody => write!(f, "Missing abstraction body"),
-            ParseError::NoTypeHat => write!(f, "Missing type hat"),
             ParseError::InvalidType => write!(f, "Invalid type"),
             ParseError::ArrowBeforeType => write!(f, "Arrow before type"),
             ParseError::NoType => write!(f, "Missing type"),
@@ -69,114 +149,386 @@ impl Display for ParseError {
             }
             ParseError::EmptyJudgement => write!(f, "Empty judgement"),
             ParseError::ExprSyntaxOutsideExpr => write!(f, "Expression syntax outside expression"),
+            ParseError::InvalidLet => {
+                write!(
+                    f,
+                    "Invalid let, should be: let <variable> = <expression> in <expression>"
+                )
+            }
+            ParseError::MissingIn => write!(f, "Missing in after let"),
+            ParseError::InvalidContext => {
+                write!(
+                    f,
+                    "Invalid context, should be: <variable> : <type>, ... ⊢ <expression> : <type>"
+                )
+            }
+            ParseError::TooManyTurnstiles => write!(f, "Too many turnstiles"),
+            ParseError::InvalidForall => {
+                write!(f, "Invalid quantifier, should be: ∀<type variable>. <type>")
+            }
+            ParseError::InvalidTypeApplication => {
+                write!(
+                    f,
+                    "Invalid type application, should be: <expression> [<type>]"
+                )
+            }
+            ParseError::UnclosedLBracket => write!(f, "Unclosed left bracket"),
+            ParseError::UnexpectedRBracket => write!(f, "Unexpected right bracket"),
+            ParseError::InvalidCase => {
+                write!(
+                    f,
+                    "Invalid case, should be: case <expression> of inl <variable> => <expression> | inr <variable> => <expression>"
+                )
+            }
+            ParseError::MissingOf => write!(f, "Missing of after case"),
+            ParseError::MissingOperand => {
+                write!(
+                    f,
+                    "Missing operand of fst, snd, inl, inr, succ, pred, iszero or fix"
+                )
+            }
+            ParseError::InvalidPair => {
+                write!(f, "Invalid pair, should be: (<expression>, <expression>)")
+            }
+            ParseError::InvalidIf => {
+                write!(
+                    f,
+                    "Invalid if, should be: if <expression> then <expression> else <expression>"
+                )
+            }
         }
     }
 }
 
-type ParseResult<T> = std::result::Result<T, ParseError>;
-
-fn _parse_type(tokens: &[Token]) -> ParseResult<Type> {
-    let mut idx = 0;
-    let mut result: Vec<Type> = Vec::new();
+impl ParseError {
+    /// The identifier of the error in the JSON output
+    fn code(&self) -> &'static str {
+        match self {
+            ParseError::EmptyExpression => "empty_expression",
+            ParseError::InvalidExpression => "invalid_expression",
+            ParseError::UnexpectedRParen => "unexpected_rparen",
+            ParseError::UnclosedLParen => "unclosed_lparen",
+            ParseError::NoAbstractionBody => "no_abstraction_body",
+            ParseError::InvalidType => "invalid_type",
+            ParseError::ArrowBeforeType => "arrow_before_type",
+            ParseError::NoType => "no_type",
+            ParseError::TypeSyntaxOutsideType => "type_syntax_outside_type",
+            ParseError::TooManyColons => "too_many_colons",
+            ParseError::JudgementTooShort => "judgement_too_short",
+            ParseError::EmptyJudgement => "empty_judgement",
+            ParseError::ExprSyntaxOutsideExpr => "expr_syntax_outside_expr",
+            ParseError::InvalidLet => "invalid_let",
+            ParseError::MissingIn => "missing_in",
+            ParseError::InvalidContext => "invalid_context",
+            ParseError::TooManyTurnstiles => "too_many_turnstiles",
+            ParseError::InvalidForall => "invalid_forall",
+            ParseError::InvalidTypeApplication => "invalid_type_application",
+            ParseError::UnclosedLBracket => "unclosed_lbracket",
+            ParseError::UnexpectedRBracket => "unexpected_rbracket",
+            ParseError::InvalidCase => "invalid_case",
+            ParseError::MissingOf => "missing_of",
+            ParseError::MissingOperand => "missing_operand",
+            ParseError::InvalidPair => "invalid_pair",
+            ParseError::InvalidIf => "invalid_if",
+        }
+    }
 
-    while idx < tokens.len() {
-        match &tokens[idx] {
-            Token::UVariable(ref var) => {
-                result.push(Type::Variable(var.clone()));
+    /// The index of the token the error is at, if it is about a single token:
+    /// the first right parenthesis (bracket) that closes nothing, or the first left one that is never closed. \
+    /// The other errors are about the structure of the expression as a whole
+    fn token(&self, tokens: &[Token]) -> Option<usize> {
+        let (open, close) = match self {
+            ParseError::UnexpectedRParen | ParseError::UnclosedLParen => {
+                (Token::LParen, Token::RParen)
             }
-            Token::Arrow => {
-                if result.is_empty() {
-                    return Err(ParseError::ArrowBeforeType);
-                }
+            ParseError::UnexpectedRBracket | ParseError::UnclosedLBracket => {
+                (Token::LBracket, Token::RBracket)
             }
-            Token::LParen => {
-                // keep track of the number of parentheses
-                let mut paren_count = 1;
-                let mut end_idx = idx + 1;
+            _ => return None,
+        };
+        let mut unclosed = Vec::new();
+        for (idx, token) in tokens.iter().enumerate() {
+            if *token == open {
+                unclosed.push(idx);
+            } else if *token == close && unclosed.pop().is_none() {
+                return Some(idx);
+            }
+        }
+        unclosed.first().copied()
+    }
 
-                // find next parentheses
-                while end_idx < tokens.len() {
-                    match tokens[end_idx] {
-                        Token::LParen => paren_count += 1,
-                        Token::RParen => {
-                            if paren_count == 1 {
-                                // recursively parse the type inside the parentheses
-                                result.push(_parse_type(&tokens[(idx + 1)..end_idx])?);
-                            }
-                            paren_count -= 1;
-                        }
-                        _ => {}
-                    }
-                    if paren_count == 0 {
-                        break;
-                    }
-                    end_idx += 1;
-                }
+    /// The error as it is reported in the JSON output, given the tokens with the line offset and column they start at. \
+    /// The column is only known if the error is at a single token
+    fn diagnostic(&self, tokens: &[Token], positions: &[Position], idx: usize) -> Diagnostic {
+        let position = self.token(tokens).and_then(|token| positions.get(token));
+        Diagnostic {
+            failure: Failure::Parse,
+            code: self.code(),
+            message: self.to_string(),
+            line: idx + position.map_or(0, |(line, _)| *line) + 1,
+            column: position.map(|(_, column)| *column),
+            underline: None,
+        }
+    }
+}
 
-                if paren_count != 0 {
-                    return Err(ParseError::UnclosedLParen);
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Whether function arrows associate to the left, as they did before. \
+/// Only a migration flag for existing corpora, the arrow associates to the right by default
+static LEFT_ASSOC_ARROWS: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_left_assoc_arrows(left: bool) {
+    LEFT_ASSOC_ARROWS.store(left, Ordering::SeqCst);
+}
+
+pub(crate) fn left_assoc_arrows() -> bool {
+    LEFT_ASSOC_ARROWS.load(Ordering::SeqCst)
+}
+
+/// Split the tokens at the separator, outside of parentheses (and brackets). \
+/// The body of a quantifier extends as far right as possible, so nothing after a ∀ is split
+fn split_top_level<'a>(tokens: &'a [Token], separator: &Token) -> ParseResult<Vec<&'a [Token]>> {
+    let mut parts = Vec::new();
+    let mut paren_count = 0;
+    let mut start = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LParen | Token::LBracket => paren_count += 1,
+            Token::RParen | Token::RBracket => {
+                if paren_count == 0 {
+                    return Err(ParseError::UnexpectedRParen);
                 }
-                idx = end_idx;
+                paren_count -= 1;
             }
-            Token::RParen => return Err(ParseError::UnexpectedRParen),
-            _ => return Err(ParseError::ExprSyntaxOutsideExpr),
+            Token::Forall if paren_count == 0 => break,
+            token if paren_count == 0 && token == separator => {
+                parts.push(&tokens[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
         }
-        idx += 1;
     }
-    if result.is_empty() {
+    if paren_count != 0 {
+        return Err(ParseError::UnclosedLParen);
+    }
+    parts.push(&tokens[start..]);
+    Ok(parts)
+}
+
+/// Parse a type: the arrow binds weakest, then +, then *. \
+/// `A * B -> A + B` is `(A * B) -> (A + B)`, and `*` and `+` associate to the right
+fn _parse_type(tokens: &[Token]) -> ParseResult<Type> {
+    if tokens.is_empty() {
         return Err(ParseError::NoType);
     }
-    if result.len() == 1 {
-        Ok(result.pop().unwrap())
-    } else {
+    let parts = split_top_level(tokens, &Token::Arrow)?;
+    if parts.len() > 1 && parts[0].is_empty() {
+        return Err(ParseError::ArrowBeforeType);
+    }
+    let result = parts
+        .into_iter()
+        .map(_parse_sum_type)
+        .collect::<ParseResult<Vec<Type>>>()?;
+
+    if left_assoc_arrows() {
+        // migration: A -> B -> C = (A -> B) -> C
         match result.into_iter().reduce(|left_type, right_type| {
             Type::Function(Box::new(left_type), Box::new(right_type))
         }) {
             Some(typ) => Ok(typ),
             None => Err(ParseError::InvalidType),
         }
+    } else {
+        // the arrow associates to the right: A -> B -> C = A -> (B -> C)
+        match result.into_iter().rev().reduce(|right_type, left_type| {
+            Type::Function(Box::new(left_type), Box::new(right_type))
+        }) {
+            Some(typ) => Ok(typ),
+            None => Err(ParseError::InvalidType),
+        }
+    }
+}
+
+/// <Type> + <Type> + ..., associating to the right
+fn _parse_sum_type(tokens: &[Token]) -> ParseResult<Type> {
+    let result = split_top_level(tokens, &Token::Plus)?
+        .into_iter()
+        .map(_parse_product_type)
+        .collect::<ParseResult<Vec<Type>>>()?;
+    match result
+        .into_iter()
+        .rev()
+        .reduce(|right_type, left_type| Type::Sum(Box::new(left_type), Box::new(right_type)))
+    {
+        Some(typ) => Ok(typ),
+        None => Err(ParseError::InvalidType),
+    }
+}
+
+/// <Type> * <Type> * ..., associating to the right
+fn _parse_product_type(tokens: &[Token]) -> ParseResult<Type> {
+    let result = split_top_level(tokens, &Token::Star)?
+        .into_iter()
+        .map(_parse_atomic_type)
+        .collect::<ParseResult<Vec<Type>>>()?;
+    match result
+        .into_iter()
+        .rev()
+        .reduce(|right_type, left_type| Type::Product(Box::new(left_type), Box::new(right_type)))
+    {
+        Some(typ) => Ok(typ),
+        None => Err(ParseError::InvalidType),
+    }
+}
+
+/// A type variable, Unit, Nat, Bool, ⊥, a negation, a quantified type or a parenthesised type
+fn _parse_atomic_type(tokens: &[Token]) -> ParseResult<Type> {
+    match tokens {
+        [] => Err(ParseError::NoType),
+        [Token::UVariable(name)] if name == "Unit" => Ok(Type::Unit),
+        [Token::UVariable(name)] if name == "Nat" => Ok(Type::Nat),
+        [Token::UVariable(name)] if name == "Bool" => Ok(Type::Bool),
+        [Token::UVariable(name)] if name == "Empty" => Ok(Type::Empty),
+        [Token::Bottom] => Ok(Type::Empty),
+        [Token::UVariable(name)] => Ok(Type::Variable(name.clone())),
+        // ¬A is short for A -> ⊥, the negation binds tighter than any connective
+        [Token::Not, rest @ ..] => Ok(Type::Function(
+            Box::new(_parse_atomic_type(rest)?),
+            Box::new(Type::Empty),
+        )),
+        // ∀X. T, like after a dot the body extends as far right as possible
+        [Token::Forall, rest @ ..] => match rest {
+            [Token::UVariable(var), Token::Dot, body @ ..] => {
+                Ok(Type::ForAll(var.clone(), Box::new(_parse_type(body)?)))
+            }
+            _ => Err(ParseError::InvalidForall),
+        },
+        [Token::LParen, inner @ .., Token::RParen] => {
+            // the first parenthesis has to be closed by the last one, (A)(B) is not a type
+            let mut paren_count = 0;
+            for token in inner {
+                match token {
+                    Token::LParen => paren_count += 1,
+                    Token::RParen if paren_count == 0 => return Err(ParseError::InvalidType),
+                    Token::RParen => paren_count -= 1,
+                    _ => {}
+                }
+            }
+            _parse_type(inner)
+        }
+        _ => {
+            let type_syntax = |token: &Token| {
+                matches!(
+                    token,
+                    Token::UVariable(_)
+                        | Token::Arrow
+                        | Token::Star
+                        | Token::Plus
+                        | Token::Forall
+                        | Token::Bottom
+                        | Token::Not
+                        | Token::Dot
+                        | Token::LParen
+                        | Token::RParen
+                )
+            };
+            if tokens.iter().all(type_syntax) {
+                Err(ParseError::InvalidType)
+            } else {
+                Err(ParseError::ExprSyntaxOutsideExpr)
+            }
+        }
     }
 }
 
+/// The optional type after the hat of a binder or injection at `idx`, e.g. λx^(A -> B). \
+/// Returns the type and the index of its last token (or `idx` if there is no type)
+fn hat_type(tokens: &[Token], idx: usize) -> ParseResult<(Option<Box<Type>>, usize)> {
+    if tokens.get(idx + 1) != Some(&Token::Hat) {
+        return Ok((None, idx));
+    }
+    // find the type
+    let mut end_idx = idx + 2;
+    let mut paren_count = 0;
+
+    while end_idx < tokens.len() {
+        match tokens[end_idx] {
+            // a negation applies to the atom after it, e.g. λx^¬A. x
+            Token::Not if paren_count == 0 => {
+                end_idx += 1;
+                continue;
+            }
+            Token::LParen => paren_count += 1,
+            Token::RParen => paren_count -= 1,
+            Token::Arrow if paren_count == 0 => {
+                return Err(ParseError::InvalidType);
+            }
+            _ => {}
+        }
+        if paren_count == 0 {
+            break;
+        }
+        end_idx += 1;
+    }
+
+    let typ = _parse_type(&tokens[idx + 2..(end_idx + 1).min(tokens.len())])?;
+    Ok((Some(Box::new(typ)), end_idx))
+}
+
+/// The index of the token that closes the construct opened at `start`, e.g. the `in` of a `let`. \
+/// Nested constructs (e.g. a let in the bound expression) are skipped
+fn matching(tokens: &[Token], start: usize, open: &Token, close: &Token) -> Option<usize> {
+    let mut count = 1;
+    for (idx, token) in tokens.iter().enumerate().skip(start + 1) {
+        if token == open {
+            count += 1;
+        } else if token == close {
+            count -= 1;
+            if count == 0 {
+                return Some(idx);
+            }
+        }
+    }
+    None
+}
+
+/// A projection, injection, absurd or PCF operator, waiting for its operand
+enum Prefix {
+    First,
+    Second,
+    Left(Option<Box<Type>>),
+    Right(Option<Box<Type>>),
+    Succ,
+    Pred,
+    IsZero,
+    Fix,
+    Absurd(Option<Box<Type>>),
+}
+
 fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
     let mut idx = 0;
     let mut result = Vec::new();
+    // projections, injections and PCF operators apply to the next atom, e.g. fst p q = (fst p) q
+    let mut prefixes: Vec<Prefix> = Vec::new();
 
     while idx < tokens.len() {
+        let results = result.len();
         match &tokens[idx] {
-            Token::Lambda(name) => {
+            lambda @ (Token::Lambda(_) | Token::TypeLambda(_)) => {
                 // If lambda is the last token, return an error
                 if idx + 1 >= tokens.len() {
                     return Err(ParseError::NoAbstractionBody);
                 }
 
-                if tokens[idx + 1] != Token::Hat {
-                    return Err(ParseError::NoTypeHat);
-                }
-
-                // find the type
-                let mut end_idx = idx + 2;
-                let mut paren_count = 0;
-
-                while end_idx < tokens.len() {
-                    match tokens[end_idx] {
-                        Token::LParen => paren_count += 1,
-                        Token::RParen => paren_count -= 1,
-                        Token::Arrow => {
-                            if paren_count == 0 {
-                                return Err(ParseError::InvalidType);
-                            }
-                        }
-                        _ => {}
-                    }
-                    if paren_count == 0 {
-                        break;
-                    }
-                    end_idx += 1;
-                }
-
-                let abstype = _parse_type(&tokens[idx + 2..end_idx + 1])?;
-                idx = end_idx;
+                // the type of the bound variable is optional (e.g. for type inference)
+                let abstype = if matches!(lambda, Token::Lambda(_)) {
+                    let (abstype, end_idx) = hat_type(tokens, idx)?;
+                    idx = end_idx;
+                    abstype
+                } else {
+                    None
+                };
 
                 // 28 DEC edit: precedence rules are other way around. λx.a b = λx.(a) b and not λx.(a b)
                 // Therefore, we find the end of the abstraction body first, and then recursively parse the body
@@ -185,7 +537,9 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
 
                 // we need to account for the type, so just looking at parentheses is not enough
                 // we set body to true once we have found a LVariable
-                if tokens[end_idx] == Token::Dot {
+                if end_idx >= tokens.len() {
+                    return Err(ParseError::NoAbstractionBody);
+                } else if tokens[end_idx] == Token::Dot {
                     end_idx = tokens.len() - 1;
                 } else {
                     let mut body = false;
@@ -194,11 +548,17 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
                             Token::LParen => paren_count += 1,
                             Token::RParen => {
                                 paren_count -= 1;
-                                if paren_count == 0 && body {
+                                // () is a body on its own
+                                if paren_count == 0
+                                    && (body || tokens[end_idx - 1] == Token::LParen)
+                                {
                                     break;
                                 }
                             }
-                            Token::LVariable(_) => {
+                            Token::LVariable(_)
+                            | Token::Numeral(_)
+                            | Token::True
+                            | Token::False => {
                                 if paren_count == 0 {
                                     break;
                                 }
@@ -214,17 +574,26 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
                 }
 
                 // recursively parse the body of the abstraction
-                let body = _parse(&tokens[idx + 1..=end_idx])?;
-                result.push(Expression::Abstraction(
-                    name.clone(),
-                    Box::new(abstype),
-                    Box::new(body),
-                ));
+                let body = Box::new(_parse(&tokens[idx + 1..=end_idx])?);
+                result.push(match lambda {
+                    Token::TypeLambda(name) => Expression::TypeAbstraction(name.clone(), body),
+                    Token::Lambda(name) => Expression::Abstraction(name.clone(), abstype, body),
+                    _ => unreachable!(),
+                });
                 idx = end_idx;
             }
             Token::LVariable(ref variable) => {
                 result.push(Expression::Variable(variable.clone()));
             }
+            Token::Numeral(n) => {
+                let mut numeral = Expression::Zero;
+                for _ in 0..*n {
+                    numeral = Expression::Succ(Box::new(numeral));
+                }
+                result.push(numeral);
+            }
+            Token::True => result.push(Expression::True),
+            Token::False => result.push(Expression::False),
             Token::LParen => {
                 // keep track of the number of parentheses
                 let mut paren_count = 1;
@@ -236,8 +605,18 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
                         Token::LParen => paren_count += 1,
                         Token::RParen => {
                             if paren_count == 1 {
-                                // recursively parse the expression inside the parentheses
-                                result.push(_parse(&tokens[(idx + 1)..end_idx])?);
+                                // recursively parse the expression, pair or unit inside the parentheses
+                                let inner = &tokens[(idx + 1)..end_idx];
+                                let parts = split_top_level(inner, &Token::Comma)?;
+                                result.push(match parts.as_slice() {
+                                    [[]] => Expression::Unit,
+                                    [expr] => _parse_ascription(expr)?,
+                                    [left, right] => Expression::Pair(
+                                        Box::new(_parse(left)?),
+                                        Box::new(_parse(right)?),
+                                    ),
+                                    _ => return Err(ParseError::InvalidPair),
+                                });
                             }
                             paren_count -= 1
                         }
@@ -258,17 +637,176 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
             Token::Colon => {
                 break;
             }
-            Token::Arrow | Token::Hat | Token::UVariable(_) => {
+            Token::LBracket => {
+                // e [T], a type application applies everything before it (application associates to the left)
+                let mut bracket_count = 1;
+                let mut end_idx = idx + 1;
+                while end_idx < tokens.len() {
+                    match tokens[end_idx] {
+                        Token::LBracket => bracket_count += 1,
+                        Token::RBracket => bracket_count -= 1,
+                        _ => {}
+                    }
+                    if bracket_count == 0 {
+                        break;
+                    }
+                    end_idx += 1;
+                }
+                if bracket_count != 0 {
+                    return Err(ParseError::UnclosedLBracket);
+                }
+
+                if !prefixes.is_empty() {
+                    return Err(ParseError::MissingOperand);
+                }
+                let typ = _parse_type(&tokens[idx + 1..end_idx])?;
+                let expr = match result.drain(..).reduce(|left_expr, right_expr| {
+                    Expression::Application(Box::new(left_expr), Box::new(right_expr))
+                }) {
+                    Some(expr) => expr,
+                    None => return Err(ParseError::InvalidTypeApplication),
+                };
+                result.push(Expression::TypeApplication(Box::new(expr), Box::new(typ)));
+                idx = end_idx;
+            }
+            Token::RBracket => return Err(ParseError::UnexpectedRBracket),
+            Token::Arrow | Token::Hat | Token::UVariable(_) | Token::Forall => {
                 return Err(ParseError::TypeSyntaxOutsideType);
             }
             Token::Dot => {
                 result.push(_parse(&tokens[idx + 1..])?);
                 idx = tokens.len();
             }
+            Token::Let => {
+                // let <Variable> = <Expression> in <Expression>
+                let name = match (tokens.get(idx + 1), tokens.get(idx + 2)) {
+                    (Some(Token::LVariable(name)), Some(Token::Equals)) => name.clone(),
+                    _ => return Err(ParseError::InvalidLet),
+                };
+
+                // find the matching in, skipping the lets nested in the bound expression
+                let end_idx = match matching(tokens, idx, &Token::Let, &Token::In) {
+                    Some(end_idx) if end_idx > idx + 2 => end_idx,
+                    _ => return Err(ParseError::MissingIn),
+                };
+
+                // like after a dot, the body extends as far right as possible
+                let bound = _parse(&tokens[idx + 3..end_idx])?;
+                let body = _parse(&tokens[end_idx + 1..])?;
+                result.push(Expression::Let(name, Box::new(bound), Box::new(body)));
+                idx = tokens.len();
+            }
+            Token::In | Token::Equals => return Err(ParseError::InvalidLet),
+            Token::Turnstile | Token::Comma => return Err(ParseError::InvalidContext),
+            Token::Fst => prefixes.push(Prefix::First),
+            Token::Snd => prefixes.push(Prefix::Second),
+            Token::Absurd => {
+                // the type of the conclusion is optional, like for an injection
+                let (typ, end_idx) = hat_type(tokens, idx)?;
+                prefixes.push(Prefix::Absurd(typ));
+                idx = end_idx;
+            }
+            injection @ (Token::Inl | Token::Inr) => {
+                // the type of the sum is optional (e.g. for type inference)
+                let (typ, end_idx) = hat_type(tokens, idx)?;
+                prefixes.push(match injection {
+                    Token::Inl => Prefix::Left(typ),
+                    _ => Prefix::Right(typ),
+                });
+                idx = end_idx;
+            }
+            Token::Case => {
+                // case <Expression> of inl <Variable> => <Expression> | inr <Variable> => <Expression>
+                // find the matching of and bar, skipping the cases nested in between
+                let of_idx = match matching(tokens, idx, &Token::Case, &Token::Of) {
+                    Some(of_idx) => of_idx,
+                    None => return Err(ParseError::MissingOf),
+                };
+                let left = match &tokens[of_idx + 1..] {
+                    [Token::Inl, Token::LVariable(left), Token::FatArrow, ..] => left.clone(),
+                    _ => return Err(ParseError::InvalidCase),
+                };
+                let bar_idx = match matching(tokens, of_idx + 3, &Token::Case, &Token::Bar) {
+                    Some(bar_idx) => bar_idx,
+                    None => return Err(ParseError::InvalidCase),
+                };
+                let right = match &tokens[bar_idx + 1..] {
+                    [Token::Inr, Token::LVariable(right), Token::FatArrow, ..] => right.clone(),
+                    _ => return Err(ParseError::InvalidCase),
+                };
+
+                // like after a dot, the last branch extends as far right as possible
+                let scrutinee = _parse(&tokens[idx + 1..of_idx])?;
+                let left_branch = _parse(&tokens[of_idx + 4..bar_idx])?;
+                let right_branch = _parse(&tokens[bar_idx + 4..])?;
+                result.push(Expression::Case(
+                    Box::new(scrutinee),
+                    left,
+                    Box::new(left_branch),
+                    right,
+                    Box::new(right_branch),
+                ));
+                idx = tokens.len();
+            }
+            Token::Of | Token::Bar | Token::FatArrow => return Err(ParseError::InvalidCase),
+            Token::Succ => prefixes.push(Prefix::Succ),
+            Token::Pred => prefixes.push(Prefix::Pred),
+            Token::IsZero => prefixes.push(Prefix::IsZero),
+            Token::Fix => prefixes.push(Prefix::Fix),
+            Token::If => {
+                // if <Expression> then <Expression> else <Expression>
+                // find the matching then and else, skipping the ifs nested in between
+                let then_idx = match matching(tokens, idx, &Token::If, &Token::Then) {
+                    Some(then_idx) => then_idx,
+                    None => return Err(ParseError::InvalidIf),
+                };
+                let else_idx = match matching(tokens, then_idx, &Token::If, &Token::Else) {
+                    Some(else_idx) => else_idx,
+                    None => return Err(ParseError::InvalidIf),
+                };
+
+                // like after a dot, the else branch extends as far right as possible
+                let condition = _parse(&tokens[idx + 1..then_idx])?;
+                let then_branch = _parse(&tokens[then_idx + 1..else_idx])?;
+                let else_branch = _parse(&tokens[else_idx + 1..])?;
+                result.push(Expression::If(
+                    Box::new(condition),
+                    Box::new(then_branch),
+                    Box::new(else_branch),
+                ));
+                idx = tokens.len();
+            }
+            Token::Then | Token::Else => return Err(ParseError::InvalidIf),
+            Token::Star | Token::Plus | Token::Bottom | Token::Not => {
+                return Err(ParseError::TypeSyntaxOutsideType)
+            }
+            Token::Square => return Err(ParseError::InvalidExpression),
+        }
+
+        // an atom was parsed, it is the operand of the waiting prefixes
+        if result.len() > results && !prefixes.is_empty() {
+            let mut expr = result.pop().unwrap();
+            while let Some(prefix) = prefixes.pop() {
+                expr = match prefix {
+                    Prefix::First => Expression::First(Box::new(expr)),
+                    Prefix::Second => Expression::Second(Box::new(expr)),
+                    Prefix::Left(typ) => Expression::Left(typ, Box::new(expr)),
+                    Prefix::Right(typ) => Expression::Right(typ, Box::new(expr)),
+                    Prefix::Succ => Expression::Succ(Box::new(expr)),
+                    Prefix::Pred => Expression::Pred(Box::new(expr)),
+                    Prefix::IsZero => Expression::IsZero(Box::new(expr)),
+                    Prefix::Fix => Expression::Fix(Box::new(expr)),
+                    Prefix::Absurd(typ) => Expression::Absurd(typ, Box::new(expr)),
+                };
+            }
+            result.push(expr);
         }
         idx += 1;
     }
 
+    if !prefixes.is_empty() {
+        return Err(ParseError::MissingOperand);
+    }
     if result.is_empty() {
         return Err(ParseError::EmptyExpression);
     }
@@ -287,91 +825,112 @@ fn _parse(tokens: &[Token]) -> ParseResult<Expression> {
     }
 }
 
-/// Display the expression in the normal format
+/// Parse the inside of parentheses, which can be an ascription: (<Expression> : <Type>)
+fn _parse_ascription(tokens: &[Token]) -> ParseResult<Expression> {
+    match split_top_level(tokens, &Token::Colon)?.as_slice() {
+        [expr] => _parse(expr),
+        [expr, typ] => Ok(Expression::Ascription(
+            Box::new(_parse(expr)?),
+            Box::new(_parse_type(typ)?),
+        )),
+        _ => Err(ParseError::TooManyColons),
+    }
+}
+
+/// Whether the tokens are a judgement, i.e. have a colon outside of parentheses (and brackets)
+/// after the context. \
+/// A colon inside parentheses is an ascription, e.g. `(λx.x : A -> A) a` is an expression,
+/// and the colons of the context are not the type, e.g. `x : A ⊢ x` is an expression in a context
+pub(crate) fn is_judgement(tokens: &[Token]) -> bool {
+    let start = tokens
+        .iter()
+        .rposition(|t| *t == Token::Turnstile)
+        .map_or(0, |pos| pos + 1);
+    let mut paren_count = 0;
+    for token in &tokens[start..] {
+        match token {
+            Token::LParen | Token::LBracket => paren_count += 1,
+            Token::RParen | Token::RBracket => paren_count -= 1,
+            Token::Colon if paren_count == 0 => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Display the expression in the normal format,
+/// with as few parentheses as possible (see printer.rs)
 /// (Used by e.g. .to_string() and .print() functions)
 impl Display for Expression {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
-        match self {
-            /*
-            If Lambda -> print λ{name}.{expr}
-            Lambda(
-                "x",
-                Application ([
-                    Variable("a"),
-                    Variable("b")
-                ])
-            )
-
-            Becomes
-
-            λx.a b
-            */
-            Expression::Abstraction(name, typ, expr) => {
-                write!(fmt, "λ{name}^{typ}.{expr}")
-            }
-            /*
-            If Variable -> print {name}
-            Variable("a")
-
-            Becomes
-
-            a
-             */
-            Expression::Variable(name) => write!(fmt, "{name}"),
-            /*
-            If Application
-                If lexpr = abs -> "({left_expr})"
-                Else -> "{left_expr}"
-                + " "
-                + If rexpr = app | abs -> "({right_expr})"
-                | Else -> "{right_expr}"
-             */
-            Expression::Application(left_expr, right_expr) => {
-                // Left
-                if let Expression::Variable(_) = left_expr.as_ref() {
-                    write!(fmt, "{left_expr}")
-                } else {
-                    write!(fmt, "({left_expr})")
-                }?;
-                // Separator
-                write!(fmt, " ")?;
-                // Right
-                match right_expr.as_ref() {
-                    Expression::Application(_, _) | Expression::Abstraction(_, _, _) => {
-                        write!(fmt, "({right_expr})")
-                    }
-
-                    r_expr => write!(fmt, "{r_expr}"),
-                }
-            }
-        }
+        write!(fmt, "{}", pretty(self, &PrintConfig::default()))
     }
 }
 
 impl Display for Type {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
-        match self {
-            Type::Function(left_type, right_type) => {
-                write!(fmt, "({left_type} -> {right_type})")
-            }
-            Type::Variable(name) => write!(fmt, "{name}"),
-        }
+        write!(fmt, "{}", type_string(self))
     }
 }
 
 impl Display for Judgement {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
-        match self {
-            Judgement::Judgement(expr, typ) => write!(fmt, "({expr}) : {typ}"),
+        write!(fmt, "{}", pretty_judgement(self, &PrintConfig::default()))
+    }
+}
+
+/// Parse the context of a judgement: <variable> : <type>, <variable> : <type>, ...
+fn _parse_context(tokens: &[Token]) -> ParseResult<Context> {
+    // an empty context, as in ⊢ e : T
+    if tokens.is_empty() {
+        return Ok(Context::new());
+    }
+    tokens
+        .split(|t| *t == Token::Comma)
+        .map(|binding| match binding {
+            [Token::LVariable(var), Token::Colon, typ @ ..] => Ok((var.clone(), _parse_type(typ)?)),
+            _ => Err(ParseError::InvalidContext),
+        })
+        .collect()
+}
+
+/// An expression on its own, without a type
+fn expression(tokens: &[Token]) -> ParseResult<Expression> {
+    // _parse stops at a colon, an expression on its own should not have one (outside of an ascription),
+    // nor a context (see open_expression)
+    if is_judgement(tokens) || tokens.contains(&Token::Turnstile) {
+        Err(ParseError::TypeSyntaxOutsideType)
+    } else {
+        _parse(tokens)
+    }
+}
+
+/// Split off the context before the turnstile, if there is one
+fn split_context(tokens: &[Token]) -> ParseResult<(Context, &[Token])> {
+    match tokens.iter().position(|t| *t == Token::Turnstile) {
+        Some(pos) => {
+            if tokens[pos + 1..].contains(&Token::Turnstile) {
+                return Err(ParseError::TooManyTurnstiles);
+            }
+            Ok((_parse_context(&tokens[..pos])?, &tokens[pos + 1..]))
         }
+        None => Ok((Context::new(), tokens)),
     }
 }
 
+/// An expression without a type, in an optional context: x : A, y : B ⊢ <Expression>
+fn open_expression(tokens: &[Token]) -> ParseResult<(Context, Expression)> {
+    let (context, tokens) = split_context(tokens)?;
+    Ok((context, expression(tokens)?))
+}
+
 fn judgement(tokens: &[Token]) -> ParseResult<Judgement> {
+    let (context, tokens) = split_context(tokens)?;
     if tokens.len() < 3 {
         return Err(ParseError::JudgementTooShort);
     }
-    let split = tokens.split(|t| *t == Token::Colon).collect::<Vec<_>>();
+    // the colons of ascriptions are inside parentheses
+    let split = split_top_level(tokens, &Token::Colon)?;
     match split.len() {
         0 => Err(ParseError::EmptyJudgement),
         1 => {
@@ -386,7 +945,7 @@ fn judgement(tokens: &[Token]) -> ParseResult<Judgement> {
         2 => {
             let expr = _parse(split[0])?;
             let typ = _parse_type(split[1])?;
-            Ok(Judgement::Judgement(Box::new(expr), Box::new(typ)))
+            Ok(Judgement::Judgement(context, Box::new(expr), Box::new(typ)))
         }
         _ => Err(ParseError::TooManyColons),
     }
@@ -416,13 +975,132 @@ pub(crate) fn parse(tokens: &[Token], idx: usize) -> Judgement {
                 idx + 1
             );
 
-            std::process::exit(1);
+            exit(Failure::Parse);
         }
         // Else: return the judgement
         Ok(judgement) => judgement,
     }
 }
 
+/// Parse the tokens into an expression without a type
+/// If given tokens result in an invalid expression, prints an error and exits the program
+///
+/// # Arguments
+/// * `tokens` - The tokens to parse
+/// * `idx` - The index of the line the tokens are on (for error printing)
+///
+/// # Returns
+/// The parsed expression
+///
+/// # Error
+/// "Invalid expression [{err_code}] caught during parsing on line {idx}!"
+pub(crate) fn parse_expression(tokens: &[Token], idx: usize) -> Expression {
+    let expression = expression(tokens);
+    dbg!(&expression);
+    match expression {
+        // If error in expression, print error and exit
+        Err(err_code) => {
+            eprintln!(
+                "Invalid expression [{}] caught during parsing on line {}!",
+                err_code,
+                idx + 1
+            );
+
+            exit(Failure::Parse);
+        }
+        // Else: return the expression
+        Ok(expression) => expression,
+    }
+}
+
+/// Parse the tokens into an expression without a type, with the context before the turnstile if there is one
+/// If given tokens result in an invalid expression, prints an error and exits the program
+///
+/// # Arguments
+/// * `tokens` - The tokens to parse
+/// * `idx` - The index of the line the tokens are on (for error printing)
+///
+/// # Returns
+/// The parsed context (empty if there is none) and expression
+///
+/// # Error
+/// "Invalid expression [{err_code}] caught during parsing on line {idx}!"
+pub(crate) fn parse_open_expression(tokens: &[Token], idx: usize) -> (Context, Expression) {
+    let expression = open_expression(tokens);
+    dbg!(&expression);
+    match expression {
+        // If error in expression, print error and exit
+        Err(err_code) => {
+            eprintln!(
+                "Invalid expression [{}] caught during parsing on line {}!",
+                err_code,
+                idx + 1
+            );
+
+            exit(Failure::Parse);
+        }
+        // Else: return the context and expression
+        Ok(expression) => expression,
+    }
+}
+
+/// Parse the tokens into a type on its own
+/// If given tokens result in an invalid type, prints an error and exits the program
+///
+/// # Arguments
+/// * `tokens` - The tokens to parse
+/// * `idx` - The index of the line the tokens are on (for error printing)
+///
+/// # Returns
+/// The parsed type
+///
+/// # Error
+/// "Invalid type [{err_code}] caught during parsing on line {idx}!"
+pub(crate) fn parse_type(tokens: &[Token], idx: usize) -> Type {
+    let typ = _parse_type(tokens);
+    dbg!(&typ);
+    match typ {
+        // If error in type, print error and exit
+        Err(err_code) => {
+            eprintln!(
+                "Invalid type [{}] caught during parsing on line {}!",
+                err_code,
+                idx + 1
+            );
+
+            exit(Failure::Parse);
+        }
+        // Else: return the type
+        Ok(typ) => typ,
+    }
+}
+
+/// Parse the tokens into a judgement. \
+/// If given tokens result in an invalid judgement, returns the error. \
+/// Only used for the JSON output, where every line is reported even if one fails
+pub(crate) fn json_parse(
+    tokens: &[Token],
+    positions: &[Position],
+    idx: usize,
+) -> std::result::Result<Judgement, Diagnostic> {
+    let judgement = judgement(tokens);
+    dbg!(&judgement);
+    judgement.map_err(|err_code| err_code.diagnostic(tokens, positions, idx))
+}
+
+/// Parse the tokens into an expression without a type, with the context before the turnstile if there is one. \
+/// If given tokens result in an invalid expression, returns the error. \
+/// Only used for the JSON output, where every line is reported even if one fails
+pub(crate) fn json_parse_open_expression(
+    tokens: &[Token],
+    positions: &[Position],
+    idx: usize,
+) -> std::result::Result<(Context, Expression), Diagnostic> {
+    let expression = open_expression(tokens);
+    dbg!(&expression);
+    expression.map_err(|err_code| err_code.diagnostic(tokens, positions, idx))
+}
+
 /// Parse the tokens into a judgement
 /// Only used for benchmarking
 /// Unwraps the result, so panics if there is an error, for ultimate speed
@@ -447,3 +1125,71 @@ pub(crate) fn manual_parse(tokens: &[Token]) -> Option<Judgement> {
         Ok(judgement) => judgement.into(),
     }
 }
+
+/// Parse the tokens into an expression without a type. \
+/// If given tokens result in an invalid expression, returns None. \
+/// Only used for manual mode (e.g. the terms of tactics), where we want to keep parsing even if there is an error
+pub(crate) fn manual_parse_expression(tokens: &[Token]) -> Option<Expression> {
+    let expression = expression(tokens);
+    dbg!(&expression);
+    match expression {
+        Err(err_code) => {
+            eprintln!("Invalid expression [{}] caught during parsing!", err_code);
+            None
+        }
+        Ok(expression) => expression.into(),
+    }
+}
+
+/// Parse the tokens into a type on its own. \
+/// If given tokens result in an invalid type, returns None. \
+/// Only used for manual mode (e.g. the goal of a proof), where we want to keep parsing even if there is an error
+pub(crate) fn manual_parse_type(tokens: &[Token]) -> Option<Type> {
+    let typ = _parse_type(tokens);
+    dbg!(&typ);
+    match typ {
+        Err(err_code) => {
+            eprintln!("Invalid type [{}] caught during parsing!", err_code);
+            None
+        }
+        Ok(typ) => typ.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tokenizer::manual_tokenize, type_checker::manual_synthesise_type};
+
+    fn var(name: &str) -> Type {
+        Type::Variable(name.to_string())
+    }
+
+    #[test]
+    fn context_colons_are_not_a_judgement() {
+        let tokens = manual_tokenize("x : A ⊢ x").unwrap();
+        assert!(!is_judgement(&tokens));
+        assert!(is_judgement(&manual_tokenize("x : A ⊢ x : A").unwrap()));
+        assert!(is_judgement(&manual_tokenize("λx^A.x : A -> A").unwrap()));
+    }
+
+    #[test]
+    fn synthesise_in_a_context() {
+        let tokens = manual_tokenize("x : A, f : A -> B |- f x").unwrap();
+        let (context, expression) = open_expression(&tokens).unwrap();
+        assert_eq!(
+            context,
+            vec![
+                ("x".to_string(), var("A")),
+                (
+                    "f".to_string(),
+                    Type::Function(Box::new(var("A")), Box::new(var("B")))
+                ),
+            ]
+        );
+        assert_eq!(
+            manual_synthesise_type(&expression, &context),
+            Some(var("B"))
+        );
+    }
+}
@@ -0,0 +1,702 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::parser::{left_assoc_arrows, Context, Expression, Judgement, Type};
+use crate::types::alpha_equivalent;
+
+/// How expressions are printed
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PrintConfig {
+    /// Print abstractions with `λ` (true) or with `\` (false)
+    pub(crate) unicode: bool,
+    /// Separate the bound variable and the body with a dot (true) or with a space (false)
+    pub(crate) dot: bool,
+    /// Target line width, `None` prints everything on one line
+    pub(crate) width: Option<usize>,
+}
+
+impl Default for PrintConfig {
+    fn default() -> Self {
+        PrintConfig {
+            unicode: true,
+            dot: true,
+            width: None,
+        }
+    }
+}
+
+/// A document in the style of Wadler's "A prettier printer". \
+/// A group is printed on one line if it fits in the remaining width,
+/// otherwise its breaks become newlines.
+#[derive(Debug)]
+enum Doc {
+    /// Literal text, never contains newlines
+    Text(String),
+    /// A possible line break, printed as the given text when its group is flat
+    Break(&'static str),
+    /// Increase the indentation of the breaks in the document
+    Nest(usize, Box<Doc>),
+    /// Documents printed after each other
+    Concat(Vec<Doc>),
+    /// Print the document flat if it fits, else break it
+    Group(Box<Doc>),
+    /// The start of the marked expression, e.g. where an error is (prints nothing)
+    MarkStart,
+    /// The end of the marked expression (prints nothing)
+    MarkEnd,
+}
+
+fn text(s: impl Into<String>) -> Doc {
+    Doc::Text(s.into())
+}
+
+fn parens(doc: Doc) -> Doc {
+    Doc::Concat(vec![text("("), doc, text(")")])
+}
+
+/// Whether the items (flattened from the given one onwards) fit in the remaining width,
+/// up to the first line break that is not flat
+fn fits(mut remaining: isize, items: &[(usize, bool, &Doc)]) -> bool {
+    let mut stack = items.to_vec();
+    while let Some((indent, flat, doc)) = stack.pop() {
+        if remaining < 0 {
+            return false;
+        }
+        match doc {
+            Doc::Text(s) => remaining -= s.chars().count() as isize,
+            Doc::Break(s) => {
+                if !flat {
+                    return true;
+                }
+                remaining -= s.chars().count() as isize;
+            }
+            Doc::Nest(i, doc) => stack.push((indent + i, flat, doc)),
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc))),
+            Doc::Group(doc) => stack.push((indent, flat, doc)),
+            Doc::MarkStart | Doc::MarkEnd => {}
+        }
+    }
+    remaining >= 0
+}
+
+/// Lay the document out within the given width
+fn render(doc: &Doc, width: Option<usize>) -> String {
+    render_marked(doc, width).0
+}
+
+/// Lay the document out within the given width,
+/// with the span (in characters from the start) of the marked expression, if there is one
+fn render_marked(doc: &Doc, width: Option<usize>) -> (String, Option<(usize, usize)>) {
+    let mut out = String::new();
+    let mut column = 0;
+    let mut length = 0;
+    let (mut start, mut end) = (None, None);
+    // (indentation, flat, document), the top of the stack is printed first
+    let mut stack = vec![(0, width.is_none(), doc)];
+
+    while let Some((indent, flat, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(s) => {
+                out.push_str(s);
+                column += s.chars().count();
+                length += s.chars().count();
+            }
+            Doc::Break(s) => {
+                if flat {
+                    out.push_str(s);
+                    column += s.chars().count();
+                    length += s.chars().count();
+                } else {
+                    out.push('\n');
+                    out.push_str(&" ".repeat(indent));
+                    column = indent;
+                    length += indent + 1;
+                }
+            }
+            Doc::Nest(i, doc) => stack.push((indent + i, flat, doc)),
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, flat, doc))),
+            Doc::Group(doc) => {
+                let remaining = width.unwrap_or(usize::MAX) as isize - column as isize;
+                let mut items = stack.clone();
+                items.push((indent, true, doc));
+                let flat = flat || fits(remaining, &items);
+                stack.push((indent, flat, doc));
+            }
+            Doc::MarkStart => start = start.or(Some(length)),
+            Doc::MarkEnd => end = end.or(Some(length)),
+        }
+    }
+    (out, start.zip(end))
+}
+
+/// Print the type with as few parentheses as the parser allows:
+/// the arrow associates to the right, so only an arrow on the left needs parentheses
+/// (or the other way around, with the `--left-assoc-arrows` migration flag). \
+/// `*` binds stronger than `+`, which binds stronger than the arrow, both associate to the right. \
+/// The body of a quantifier extends as far right as possible, so it needs parentheses unless nothing follows it
+pub(crate) fn type_string(typ: &Type) -> String {
+    type_text(typ, true)
+}
+
+/// Print the type, with `∀` (unicode) or with `forall`
+fn type_text(typ: &Type, unicode: bool) -> String {
+    type_prec(typ, 0, true, unicode)
+}
+
+/// Print the type where an operator of at least the given precedence is expected
+/// (0: ∀, 1: ->, 2: +, 3: *, 4: atom), `rightmost` if nothing follows it
+fn type_prec(typ: &Type, prec: usize, rightmost: bool, unicode: bool) -> String {
+    let (own_prec, text) = match typ {
+        Type::Variable(name) => (4, name.clone()),
+        Type::Unknown(n) => (4, unknown_name(*n)),
+        Type::Unit => (4, "Unit".to_string()),
+        Type::Nat => (4, "Nat".to_string()),
+        Type::Bool => (4, "Bool".to_string()),
+        Type::Empty if unicode => (4, "⊥".to_string()),
+        Type::Empty => (4, "Empty".to_string()),
+        Type::ForAll(var, body) => {
+            let forall = if unicode { "∀" } else { "forall " };
+            let body = type_prec(body, 0, true, unicode);
+            // the body extends as far right as possible, so a quantifier is only safe at the end
+            let own_prec = if rightmost { 4 } else { 0 };
+            (own_prec, format!("{forall}{var}. {body}"))
+        }
+        Type::Function(left_type, right_type)
+        | Type::Product(left_type, right_type)
+        | Type::Sum(left_type, right_type) => {
+            let (own_prec, operator) = match typ {
+                Type::Function(_, _) => (1, "->"),
+                Type::Sum(_, _) => (2, "+"),
+                _ => (3, "*"),
+            };
+            // inside parentheses nothing follows the type
+            let rightmost = rightmost || prec > own_prec;
+            let (left_prec, right_prec) = match typ {
+                Type::Function(_, _) if left_assoc_arrows() => (own_prec, own_prec + 1),
+                _ => (own_prec + 1, own_prec),
+            };
+            let left = type_prec(left_type, left_prec, false, unicode);
+            let right = type_prec(right_type, right_prec, rightmost, unicode);
+            (own_prec, format!("{left} {operator} {right}"))
+        }
+    };
+    if own_prec < prec {
+        format!("({text})")
+    } else {
+        text
+    }
+}
+
+/// Print the type as a proposition, read through the Curry-Howard correspondence:
+/// a product is a conjunction `∧`, a sum a disjunction `∨`, Unit is truth `⊤`
+/// and a function into `⊥` is a negation `¬`. \
+/// The precedences are those of the types (∀, ->, ∨, ∧), negation binds tighter than all of them
+pub(crate) fn proposition_string(typ: &Type) -> String {
+    proposition_prec(typ, 0, true)
+}
+
+/// Print the proposition where an operator of at least the given precedence is expected
+/// (0: ∀, 1: ->, 2: ∨, 3: ∧, 4: ¬ or atom), `rightmost` if nothing follows it
+fn proposition_prec(typ: &Type, prec: usize, rightmost: bool) -> String {
+    let (own_prec, text) = match typ {
+        Type::Unit => (4, "⊤".to_string()),
+        Type::Function(left_type, right_type) if **right_type == Type::Empty => {
+            (4, format!("¬{}", proposition_prec(left_type, 4, false)))
+        }
+        Type::ForAll(var, body) => {
+            let body = proposition_prec(body, 0, true);
+            let own_prec = if rightmost { 4 } else { 0 };
+            (own_prec, format!("∀{var}. {body}"))
+        }
+        Type::Function(left_type, right_type)
+        | Type::Product(left_type, right_type)
+        | Type::Sum(left_type, right_type) => {
+            let (own_prec, operator) = match typ {
+                Type::Function(_, _) => (1, "->"),
+                Type::Sum(_, _) => (2, "∨"),
+                _ => (3, "∧"),
+            };
+            let rightmost = rightmost || prec > own_prec;
+            let (left_prec, right_prec) = match typ {
+                Type::Function(_, _) if left_assoc_arrows() => (own_prec, own_prec + 1),
+                _ => (own_prec + 1, own_prec),
+            };
+            let left = proposition_prec(left_type, left_prec, false);
+            let right = proposition_prec(right_type, right_prec, rightmost);
+            (own_prec, format!("{left} {operator} {right}"))
+        }
+        _ => (4, type_prec(typ, 4, rightmost, true)),
+    };
+    if own_prec < prec {
+        format!("({text})")
+    } else {
+        text
+    }
+}
+
+/// Print the type, with a line that underlines where it differs from the other type
+/// (e.g. the type of a judgement and the type its expression has)
+pub(crate) fn type_diff(typ: &Type, other: &Type) -> (String, String) {
+    diff_prec(typ, other, 0, true)
+}
+
+/// Print the type like `type_prec`, with a line that underlines the parts that differ from the other type. \
+/// Types with the same operator are compared part by part, anything else differs as a whole
+fn diff_prec(typ: &Type, other: &Type, prec: usize, rightmost: bool) -> (String, String) {
+    let text = type_prec(typ, prec, rightmost, true);
+    if alpha_equivalent(typ, other) {
+        let marks = " ".repeat(text.chars().count());
+        return (text, marks);
+    }
+    let (own_prec, text, marks) = match (typ, other) {
+        (Type::Function(left_type, right_type), Type::Function(left_other, right_other))
+        | (Type::Sum(left_type, right_type), Type::Sum(left_other, right_other))
+        | (Type::Product(left_type, right_type), Type::Product(left_other, right_other)) => {
+            let (own_prec, operator) = match typ {
+                Type::Function(_, _) => (1, "->"),
+                Type::Sum(_, _) => (2, "+"),
+                _ => (3, "*"),
+            };
+            let rightmost = rightmost || prec > own_prec;
+            let (left_prec, right_prec) = match typ {
+                Type::Function(_, _) if left_assoc_arrows() => (own_prec, own_prec + 1),
+                _ => (own_prec + 1, own_prec),
+            };
+            let (left, left_marks) = diff_prec(left_type, left_other, left_prec, false);
+            let (right, right_marks) = diff_prec(right_type, right_other, right_prec, rightmost);
+            let space = " ".repeat(operator.chars().count() + 2);
+            (
+                own_prec,
+                format!("{left} {operator} {right}"),
+                format!("{left_marks}{space}{right_marks}"),
+            )
+        }
+        (Type::ForAll(var, body), Type::ForAll(other_var, other_body)) if var == other_var => {
+            let (body, body_marks) = diff_prec(body, other_body, 0, true);
+            let own_prec = if rightmost { 4 } else { 0 };
+            let binder = format!("∀{var}. ");
+            let space = " ".repeat(binder.chars().count());
+            (
+                own_prec,
+                format!("{binder}{body}"),
+                format!("{space}{body_marks}"),
+            )
+        }
+        _ => {
+            let marks = "^".repeat(text.chars().count());
+            return (text, marks);
+        }
+    };
+    if own_prec < prec {
+        (format!("({text})"), format!(" {marks} "))
+    } else {
+        (text, marks)
+    }
+}
+
+/// Print the type as a single variable or a parenthesised type (e.g. after a type hat)
+fn atomic_type(typ: &Type, unicode: bool) -> String {
+    type_prec(typ, 4, false, unicode)
+}
+
+/// The name of an unknown type: a, b, ..., z, a1, b1, ...
+pub(crate) fn unknown_name(n: usize) -> String {
+    let letter = (b'a' + (n % 26) as u8) as char;
+    match n / 26 {
+        0 => letter.to_string(),
+        round => format!("{letter}{round}"),
+    }
+}
+
+/// Build the document of an expression, with as few parentheses as the parser allows.
+///
+/// Application associates to the left, so only an application on the right needs parentheses. \
+/// With a dot the body of an abstraction extends as far right as possible,
+/// so an abstraction needs parentheses unless nothing follows it (`rightmost`). \
+/// Without a dot the body is a single variable, abstraction or parenthesised expression,
+/// so an abstraction never needs parentheses, but an application or let as its body does. \
+/// The body of a let always extends as far right as possible.
+fn expression_doc(
+    expr: &Expression,
+    rightmost: bool,
+    config: &PrintConfig,
+    mark: Option<&Expression>,
+) -> Doc {
+    match expr {
+        Expression::Variable(name) => text(name),
+        Expression::Abstraction(_, _, _) | Expression::TypeAbstraction(_, _) => {
+            // a chain of (type) abstractions shares one line: λx.λy.ΛZ.
+            let lambda = if config.unicode { "λ" } else { "\\" };
+            let type_lambda = if config.unicode { "Λ" } else { "/\\" };
+            let separator = if config.dot { "." } else { " " };
+            let mut binders = String::new();
+            let mut body = expr;
+            // where the marked abstraction starts, if it is inside the chain
+            let mut marked_at = None;
+            loop {
+                if !std::ptr::eq(body, expr) && mark.is_some_and(|mark| std::ptr::eq(body, mark)) {
+                    marked_at = Some(binders.len());
+                }
+                match body {
+                    Expression::Abstraction(name, Some(typ), inner) => {
+                        let typ = atomic_type(typ, config.unicode);
+                        binders.push_str(&format!("{lambda}{name}^{typ}{separator}"));
+                        body = inner;
+                    }
+                    Expression::Abstraction(name, None, inner) => {
+                        binders.push_str(&format!("{lambda}{name}{separator}"));
+                        body = inner;
+                    }
+                    Expression::TypeAbstraction(name, inner) => {
+                        binders.push_str(&format!("{type_lambda}{name}{separator}"));
+                        body = inner;
+                    }
+                    _ => break,
+                }
+            }
+            // the break takes over the role of the space separator
+            if !config.dot {
+                binders.pop();
+            }
+
+            let body_doc = match body {
+                Expression::Application(_, _)
+                | Expression::TypeApplication(_, _)
+                | Expression::Let(_, _, _)
+                | Expression::Case(_, _, _, _, _)
+                | Expression::If(_, _, _)
+                    if !config.dot =>
+                {
+                    parens(marked_doc(body, true, config, mark))
+                }
+                _ => marked_doc(body, true, config, mark),
+            };
+            let (binders, body_doc) = match marked_at {
+                Some(at) => (
+                    Doc::Concat(vec![
+                        text(&binders[..at]),
+                        Doc::MarkStart,
+                        text(&binders[at..]),
+                    ]),
+                    Doc::Concat(vec![body_doc, Doc::MarkEnd]),
+                ),
+                None => (text(binders), body_doc),
+            };
+            let doc = Doc::Group(Box::new(Doc::Concat(vec![
+                binders,
+                Doc::Nest(
+                    2,
+                    Box::new(Doc::Concat(vec![
+                        Doc::Break(if config.dot { "" } else { " " }),
+                        body_doc,
+                    ])),
+                ),
+            ])));
+            if config.dot && !rightmost {
+                parens(doc)
+            } else {
+                doc
+            }
+        }
+        Expression::Application(_, _) | Expression::TypeApplication(_, _) => {
+            // flatten the left-associative spine: ((f a) [T]) c = f [a, [T], c]
+            let mut args = Vec::new();
+            let mut head = expr;
+            // the number of arguments outside of the marked application, if it is inside the spine
+            let mut marked_outer = None;
+            loop {
+                if !std::ptr::eq(head, expr) && mark.is_some_and(|mark| std::ptr::eq(head, mark)) {
+                    marked_outer = Some(args.len());
+                }
+                match head {
+                    Expression::Application(left_expr, right_expr) => {
+                        args.push(Ok(right_expr.as_ref()));
+                        head = left_expr;
+                    }
+                    Expression::TypeApplication(left_expr, typ) => {
+                        args.push(Err(typ.as_ref()));
+                        head = left_expr;
+                    }
+                    _ => break,
+                }
+            }
+            args.reverse();
+            // the marked application covers the head and the arguments up to this one
+            let marked_last = marked_outer.map(|outer| args.len() - outer - 1);
+
+            let mut rest = Vec::new();
+            let last = args.len() - 1;
+            for (i, arg) in args.into_iter().enumerate() {
+                rest.push(Doc::Break(" "));
+                rest.push(match arg {
+                    // the type of a type application is delimited by its brackets
+                    Err(typ) => text(format!("[{}]", type_text(typ, config.unicode))),
+                    Ok(arg) if is_application(arg) => parens(marked_doc(arg, true, config, mark)),
+                    Ok(arg) => marked_doc(arg, rightmost && i == last, config, mark),
+                });
+                if marked_last == Some(i) {
+                    rest.push(Doc::MarkEnd);
+                }
+            }
+            let head = marked_doc(head, false, config, mark);
+            let head = match marked_last {
+                Some(_) => Doc::Concat(vec![Doc::MarkStart, head]),
+                None => head,
+            };
+            Doc::Group(Box::new(Doc::Concat(vec![
+                head,
+                Doc::Nest(2, Box::new(Doc::Concat(rest))),
+            ])))
+        }
+        Expression::Let(name, bound, body) => {
+            // like an abstraction with a dot, the body extends as far right as possible
+            let doc = Doc::Group(Box::new(Doc::Concat(vec![
+                text(format!("let {name} =")),
+                Doc::Nest(
+                    2,
+                    Box::new(Doc::Concat(vec![
+                        Doc::Break(" "),
+                        marked_doc(bound, true, config, mark),
+                    ])),
+                ),
+                Doc::Break(" "),
+                text("in"),
+                Doc::Break(" "),
+                marked_doc(body, true, config, mark),
+            ])));
+            if rightmost {
+                doc
+            } else {
+                parens(doc)
+            }
+        }
+        Expression::Unit => text("()"),
+        Expression::Pair(left, right) => Doc::Group(Box::new(Doc::Concat(vec![
+            text("("),
+            Doc::Nest(
+                1,
+                Box::new(Doc::Concat(vec![
+                    marked_doc(left, true, config, mark),
+                    text(","),
+                    Doc::Break(" "),
+                    marked_doc(right, true, config, mark),
+                ])),
+            ),
+            text(")"),
+        ]))),
+        Expression::Zero => text("0"),
+        Expression::Succ(_) if numeral(expr).is_some() => text(numeral(expr).unwrap().to_string()),
+        Expression::True => text("true"),
+        Expression::False => text("false"),
+        Expression::First(operand)
+        | Expression::Second(operand)
+        | Expression::Left(_, operand)
+        | Expression::Right(_, operand)
+        | Expression::Succ(operand)
+        | Expression::Pred(operand)
+        | Expression::IsZero(operand)
+        | Expression::Fix(operand)
+        | Expression::Absurd(_, operand) => {
+            let operator = match expr {
+                Expression::First(_) => "fst".to_string(),
+                Expression::Second(_) => "snd".to_string(),
+                Expression::Succ(_) => "succ".to_string(),
+                Expression::Pred(_) => "pred".to_string(),
+                Expression::IsZero(_) => "iszero".to_string(),
+                Expression::Fix(_) => "fix".to_string(),
+                Expression::Left(Some(typ), _) => {
+                    format!("inl^{}", atomic_type(typ, config.unicode))
+                }
+                Expression::Left(None, _) => "inl".to_string(),
+                Expression::Right(Some(typ), _) => {
+                    format!("inr^{}", atomic_type(typ, config.unicode))
+                }
+                Expression::Right(None, _) => "inr".to_string(),
+                Expression::Absurd(Some(typ), _) => {
+                    format!("absurd^{}", atomic_type(typ, config.unicode))
+                }
+                _ => "absurd".to_string(),
+            };
+            // the operand is a single atom
+            let operand = match operand.as_ref() {
+                Expression::Variable(_)
+                | Expression::Unit
+                | Expression::Pair(_, _)
+                | Expression::Ascription(_, _)
+                | Expression::Zero
+                | Expression::True
+                | Expression::False => marked_doc(operand, true, config, mark),
+                operand if numeral(operand).is_some() => marked_doc(operand, true, config, mark),
+                _ => parens(marked_doc(operand, true, config, mark)),
+            };
+            Doc::Concat(vec![text(operator), text(" "), operand])
+        }
+        Expression::Case(scrutinee, left, left_branch, right, right_branch) => {
+            // like a let, the last branch extends as far right as possible
+            let doc = Doc::Group(Box::new(Doc::Concat(vec![
+                text("case "),
+                marked_doc(scrutinee, true, config, mark),
+                text(" of"),
+                Doc::Nest(
+                    2,
+                    Box::new(Doc::Concat(vec![
+                        Doc::Break(" "),
+                        text(format!("inl {left} => ")),
+                        marked_doc(left_branch, true, config, mark),
+                        Doc::Break(" "),
+                        text(format!("| inr {right} => ")),
+                        marked_doc(right_branch, true, config, mark),
+                    ])),
+                ),
+            ])));
+            if rightmost {
+                doc
+            } else {
+                parens(doc)
+            }
+        }
+        Expression::If(condition, then_branch, else_branch) => {
+            // like a let, the else branch extends as far right as possible
+            let doc = Doc::Group(Box::new(Doc::Concat(vec![
+                text("if "),
+                marked_doc(condition, true, config, mark),
+                Doc::Nest(
+                    2,
+                    Box::new(Doc::Concat(vec![
+                        Doc::Break(" "),
+                        text("then "),
+                        marked_doc(then_branch, true, config, mark),
+                        Doc::Break(" "),
+                        text("else "),
+                        marked_doc(else_branch, true, config, mark),
+                    ])),
+                ),
+            ])));
+            if rightmost {
+                doc
+            } else {
+                parens(doc)
+            }
+        }
+        Expression::Ascription(inner, typ) => Doc::Group(Box::new(Doc::Concat(vec![
+            text("("),
+            Doc::Nest(
+                1,
+                Box::new(Doc::Concat(vec![
+                    marked_doc(inner, true, config, mark),
+                    Doc::Break(" "),
+                    text(format!(": {}", type_text(typ, config.unicode))),
+                ])),
+            ),
+            text(")"),
+        ]))),
+    }
+}
+
+/// Build the document of an expression, between marks if it is the marked expression
+/// (the very same one, not just an equal one)
+fn marked_doc(
+    expr: &Expression,
+    rightmost: bool,
+    config: &PrintConfig,
+    mark: Option<&Expression>,
+) -> Doc {
+    let doc = expression_doc(expr, rightmost, config, mark);
+    match mark {
+        Some(mark) if std::ptr::eq(expr, mark) => {
+            Doc::Concat(vec![Doc::MarkStart, doc, Doc::MarkEnd])
+        }
+        _ => doc,
+    }
+}
+
+/// Whether the expression is an application of an expression, a type, a projection, an injection,
+/// absurd or a PCF operator, which needs parentheses as an argument
+fn is_application(expr: &Expression) -> bool {
+    match expr {
+        Expression::Succ(_) => numeral(expr).is_none(),
+        _ => matches!(
+            expr,
+            Expression::Application(_, _)
+                | Expression::TypeApplication(_, _)
+                | Expression::First(_)
+                | Expression::Second(_)
+                | Expression::Left(_, _)
+                | Expression::Right(_, _)
+                | Expression::Pred(_)
+                | Expression::IsZero(_)
+                | Expression::Fix(_)
+                | Expression::Absurd(_, _)
+        ),
+    }
+}
+
+/// The number a chain of successors of zero stands for, e.g. 2 for `succ (succ 0)`
+fn numeral(expr: &Expression) -> Option<usize> {
+    match expr {
+        Expression::Zero => Some(0),
+        Expression::Succ(inner) => numeral(inner).map(|n| n + 1),
+        _ => None,
+    }
+}
+
+/// Print the expression according to the given configuration
+pub(crate) fn pretty(expr: &Expression, config: &PrintConfig) -> String {
+    let doc = expression_doc(expr, true, config, None);
+    let out = render(&doc, config.width);
+    // a broken layout is wrapped in parentheses, so its lines are read back as one expression
+    if out.contains('\n') {
+        render(&parens(doc), config.width)
+    } else {
+        out
+    }
+}
+
+/// Print the expression on one line, with a line that underlines the marked expression in it
+/// (empty if the marked expression is not part of it)
+pub(crate) fn underline(expr: &Expression, mark: &Expression) -> (String, String) {
+    let doc = expression_doc(expr, true, &PrintConfig::default(), Some(mark));
+    let doc = match std::ptr::eq(expr, mark) {
+        true => Doc::Concat(vec![Doc::MarkStart, doc, Doc::MarkEnd]),
+        false => doc,
+    };
+    let (out, span) = render_marked(&doc, None);
+    let marks = match span {
+        Some((start, end)) => format!("{}{}", " ".repeat(start), "^".repeat(end - start)),
+        None => String::new(),
+    };
+    (out, marks)
+}
+
+/// Print the judgement according to the given configuration
+pub(crate) fn pretty_judgement(judgement: &Judgement, config: &PrintConfig) -> String {
+    let Judgement::Judgement(context, expr, typ) = judgement;
+    let judgement = format!(
+        "{} : {}",
+        pretty(expr, config),
+        type_text(typ, config.unicode)
+    );
+    in_context(context, judgement, config)
+}
+
+/// Print the expression in its context, `x : A ⊢ e`, or only the expression if the context is empty
+pub(crate) fn pretty_open_expression(
+    context: &Context,
+    expr: &Expression,
+    config: &PrintConfig,
+) -> String {
+    in_context(context, pretty(expr, config), config)
+}
+
+/// Put the context and a turnstile before the printed text, unless the context is empty
+fn in_context(context: &Context, text: String, config: &PrintConfig) -> String {
+    if context.is_empty() {
+        return text;
+    }
+    let context = context
+        .iter()
+        .map(|(var, typ)| format!("{} : {}", var, type_text(typ, config.unicode)))
+        .collect::<Vec<String>>();
+    let turnstile = if config.unicode { "⊢" } else { "|-" };
+    format!("{} {} {}", context.join(", "), turnstile, text)
+}
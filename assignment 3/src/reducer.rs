@@ -0,0 +1,709 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::dbg;
+use crate::json::Diagnostic;
+use crate::utils::{exit, Failure};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result};
+use std::sync::atomic::AtomicUsize;
+
+use crate::parser::{Expression, Type};
+use crate::types::{is_free_in, substitute_type};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum ReduceError {
+    ReductionOutOfBounds,
+}
+
+const MAX_REDUCTIONS: usize = 10000;
+
+impl Display for ReduceError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            ReduceError::ReductionOutOfBounds => {
+                write!(
+                    f,
+                    "Reduction out of bounds, more than {} reduction steps",
+                    MAX_REDUCTIONS
+                )
+            }
+        }
+    }
+}
+
+type ReduceResult = std::result::Result<Expression, ReduceError>;
+
+// Global counter for alpha conversion variable names
+// An easy way to make sure that the variable names are always unique
+static CUSTOM_VARNAME_COUNTER: AtomicUsize = AtomicUsize::new(1);
+
+/// A variant of the name with the next number of the counter, e.g. `x1`, `x2`, ...,
+/// skipping the names that are `taken` (e.g. that occur free where the name is bound)
+pub(crate) fn unique_varname(old_name: &str, taken: impl Fn(&str) -> bool) -> String {
+    loop {
+        let count = CUSTOM_VARNAME_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let new_name = format!("{}{}", old_name, count);
+        if !taken(&new_name) {
+            return new_name;
+        }
+    }
+}
+
+/// A term with variables and binders, so the capture-avoiding substitution below
+/// also works for other kinds of terms (e.g. those of the calculus of constructions)
+pub(crate) trait Substitute: Sized {
+    /// The term that is just the variable
+    fn variable(name: String) -> Self;
+    /// Whether the variable occurs free in the term
+    fn is_free(&self, var: &str) -> bool;
+    /// Substitute the term for the free occurrences of the variable, avoiding capture
+    fn substitute(self, symbol: &str, subterm: &Self) -> Self;
+}
+
+impl Substitute for Expression {
+    fn variable(name: String) -> Self {
+        Expression::Variable(name)
+    }
+
+    fn is_free(&self, var: &str) -> bool {
+        is_free_var(self, var)
+    }
+
+    fn substitute(self, symbol: &str, subterm: &Self) -> Self {
+        substitution(self, symbol, subterm)
+    }
+}
+
+/// Rename the bound variable if it would capture a free variable of the substituted term,
+/// returns the (new) name of the variable and the body it binds in. \
+/// The new name is free in neither the body nor the substituted term, and is not the substituted variable,
+/// so it captures nothing itself
+fn rename_bound<T: Substitute>(var: String, body: T, symbol: &str, subterm: &T) -> (String, T) {
+    if subterm.is_free(&var) {
+        // alpha conversion
+        let new_name = unique_varname(&var, |name| {
+            name == symbol || body.is_free(name) || subterm.is_free(name)
+        });
+        let body = body.substitute(&var, &T::variable(new_name.clone()));
+        (new_name, body)
+    } else {
+        (var, body)
+    }
+}
+
+/// Substitute the term in the body of a binder of the variable,
+/// returns the (new) name of the variable and the substituted body
+pub(crate) fn substitute_binder<T: Substitute>(
+    var: String,
+    body: T,
+    symbol: &str,
+    subterm: &T,
+) -> (String, T) {
+    // the variable is shadowed, so it has no free occurrences in the body
+    if var == symbol {
+        return (var, body);
+    }
+    let (var, body) = rename_bound(var, body, symbol, subterm);
+    (var, body.substitute(symbol, subterm))
+}
+
+/// Substitute the expression for the free occurrences of the variable, avoiding capture
+fn substitution(expr: Expression, symbol: &str, subexp: &Expression) -> Expression {
+    let subst = |expr: Box<Expression>| Box::new(substitution(*expr, symbol, subexp));
+    match expr {
+        Expression::Application(lexpr, rexpr) => {
+            Expression::Application(subst(lexpr), subst(rexpr))
+        }
+        Expression::Abstraction(var, typ, body) => {
+            let (var, body) = substitute_binder(var, *body, symbol, subexp);
+            Expression::Abstraction(var, typ, Box::new(body))
+        }
+        Expression::Variable(varname) => {
+            if varname == symbol {
+                subexp.clone()
+            } else {
+                Expression::Variable(varname)
+            }
+        }
+        Expression::Let(var, bound, body) => {
+            let (var, body) = substitute_binder(var, *body, symbol, subexp);
+            Expression::Let(var, subst(bound), Box::new(body))
+        }
+        Expression::TypeAbstraction(var, body) => Expression::TypeAbstraction(var, subst(body)),
+        Expression::TypeApplication(lexpr, typ) => Expression::TypeApplication(subst(lexpr), typ),
+        Expression::Unit => Expression::Unit,
+        Expression::Pair(left, right) => Expression::Pair(subst(left), subst(right)),
+        Expression::First(pair) => Expression::First(subst(pair)),
+        Expression::Second(pair) => Expression::Second(subst(pair)),
+        Expression::Left(typ, inner) => Expression::Left(typ, subst(inner)),
+        Expression::Right(typ, inner) => Expression::Right(typ, subst(inner)),
+        Expression::Case(scrutinee, left, left_branch, right, right_branch) => {
+            let (left, left_branch) = substitute_binder(left, *left_branch, symbol, subexp);
+            let (right, right_branch) = substitute_binder(right, *right_branch, symbol, subexp);
+            Expression::Case(
+                subst(scrutinee),
+                left,
+                Box::new(left_branch),
+                right,
+                Box::new(right_branch),
+            )
+        }
+        Expression::Ascription(inner, typ) => Expression::Ascription(subst(inner), typ),
+        Expression::Zero | Expression::True | Expression::False => expr,
+        Expression::Succ(inner) => Expression::Succ(subst(inner)),
+        Expression::Pred(inner) => Expression::Pred(subst(inner)),
+        Expression::IsZero(inner) => Expression::IsZero(subst(inner)),
+        Expression::If(condition, then_branch, else_branch) => {
+            Expression::If(subst(condition), subst(then_branch), subst(else_branch))
+        }
+        Expression::Fix(function) => Expression::Fix(subst(function)),
+        Expression::Absurd(typ, inner) => Expression::Absurd(typ, subst(inner)),
+    }
+}
+
+/// Substitute the type for the free occurrences of the type variable in the annotations of the expression
+fn type_substitution(expr: Expression, var: &str, typ: &Type) -> Expression {
+    let subst = |expr: Box<Expression>| Box::new(type_substitution(*expr, var, typ));
+    let subst_type = |annotation: Option<Box<Type>>| {
+        annotation.map(|annotation| Box::new(substitute_type(&annotation, var, typ)))
+    };
+    match expr {
+        Expression::Application(lexpr, rexpr) => {
+            Expression::Application(subst(lexpr), subst(rexpr))
+        }
+        Expression::Abstraction(name, annotation, body) => {
+            Expression::Abstraction(name, subst_type(annotation), subst(body))
+        }
+        Expression::Variable(_)
+        | Expression::Unit
+        | Expression::Zero
+        | Expression::True
+        | Expression::False => expr,
+        Expression::Let(name, bound, body) => Expression::Let(name, subst(bound), subst(body)),
+        Expression::TypeAbstraction(bound, body) => {
+            // the type variable is shadowed, so it has no free occurrences in the body
+            if bound == var {
+                return Expression::TypeAbstraction(bound, body);
+            }
+            if is_free_in(&bound, typ) {
+                // alpha conversion
+                // a type variable occurs free in the body if substituting it changes the body
+                let new_name = unique_varname(&bound, |name| {
+                    name == var
+                        || is_free_in(name, typ)
+                        || type_substitution((*body).clone(), name, &Type::Unit) != *body
+                });
+                let body = type_substitution(*body, &bound, &Type::Variable(new_name.clone()));
+                Expression::TypeAbstraction(new_name, subst(Box::new(body)))
+            } else {
+                Expression::TypeAbstraction(bound, subst(body))
+            }
+        }
+        Expression::TypeApplication(lexpr, argument) => Expression::TypeApplication(
+            subst(lexpr),
+            Box::new(substitute_type(&argument, var, typ)),
+        ),
+        Expression::Pair(left, right) => Expression::Pair(subst(left), subst(right)),
+        Expression::First(pair) => Expression::First(subst(pair)),
+        Expression::Second(pair) => Expression::Second(subst(pair)),
+        Expression::Left(annotation, inner) => {
+            Expression::Left(subst_type(annotation), subst(inner))
+        }
+        Expression::Right(annotation, inner) => {
+            Expression::Right(subst_type(annotation), subst(inner))
+        }
+        Expression::Case(scrutinee, left, left_branch, right, right_branch) => Expression::Case(
+            subst(scrutinee),
+            left,
+            subst(left_branch),
+            right,
+            subst(right_branch),
+        ),
+        Expression::Ascription(inner, ascription) => Expression::Ascription(
+            subst(inner),
+            Box::new(substitute_type(&ascription, var, typ)),
+        ),
+        Expression::Succ(inner) => Expression::Succ(subst(inner)),
+        Expression::Pred(inner) => Expression::Pred(subst(inner)),
+        Expression::IsZero(inner) => Expression::IsZero(subst(inner)),
+        Expression::If(condition, then_branch, else_branch) => {
+            Expression::If(subst(condition), subst(then_branch), subst(else_branch))
+        }
+        Expression::Fix(function) => Expression::Fix(subst(function)),
+        Expression::Absurd(annotation, inner) => {
+            Expression::Absurd(subst_type(annotation), subst(inner))
+        }
+    }
+}
+
+fn is_free_var(expression: &Expression, symbol: &str) -> bool {
+    let mut free = HashSet::new();
+    _free_vars(expression, &mut free, &mut Vec::new());
+    free.contains(symbol)
+}
+
+/// Collect the free variables of the expression, `bound` are the variables bound around it
+fn _free_vars(expression: &Expression, free: &mut HashSet<String>, bound: &mut Vec<String>) {
+    // the free variables of a body in which the variable is bound
+    fn binding(var: &str, body: &Expression, free: &mut HashSet<String>, bound: &mut Vec<String>) {
+        bound.push(var.to_string());
+        _free_vars(body, free, bound);
+        bound.pop();
+    }
+    match expression {
+        Expression::Application(lexpr, rexpr) | Expression::Pair(lexpr, rexpr) => {
+            _free_vars(lexpr, free, bound);
+            _free_vars(rexpr, free, bound);
+        }
+        Expression::Abstraction(var, _, body) => binding(var, body, free, bound),
+        Expression::Variable(varname) => {
+            if !bound.contains(varname) {
+                free.insert(varname.clone());
+            }
+        }
+        Expression::Let(var, bound_expr, body) => {
+            _free_vars(bound_expr, free, bound);
+            binding(var, body, free, bound);
+        }
+        Expression::TypeAbstraction(_, body)
+        | Expression::TypeApplication(body, _)
+        | Expression::First(body)
+        | Expression::Second(body)
+        | Expression::Left(_, body)
+        | Expression::Right(_, body)
+        | Expression::Ascription(body, _)
+        | Expression::Succ(body)
+        | Expression::Pred(body)
+        | Expression::IsZero(body)
+        | Expression::Fix(body)
+        | Expression::Absurd(_, body) => _free_vars(body, free, bound),
+        Expression::Unit | Expression::Zero | Expression::True | Expression::False => {}
+        Expression::If(condition, then_branch, else_branch) => {
+            _free_vars(condition, free, bound);
+            _free_vars(then_branch, free, bound);
+            _free_vars(else_branch, free, bound);
+        }
+        Expression::Case(scrutinee, left, left_branch, right, right_branch) => {
+            _free_vars(scrutinee, free, bound);
+            binding(left, left_branch, free, bound);
+            binding(right, right_branch, free, bound);
+        }
+    }
+}
+
+/// Counts the number of reductions
+///
+static REDUCE_COUNTER: AtomicUsize = AtomicUsize::new(1);
+
+/// Whether the expression needs a type to be checked against, because it cannot synthesise its own,
+/// e.g. `λx.x` or `inl a`, but not `λx^A.x` or `(λx.x : A -> A)`
+fn needs_type(expr: &Expression) -> bool {
+    match expr {
+        Expression::Abstraction(_, None, _)
+        | Expression::Left(None, _)
+        | Expression::Right(None, _)
+        | Expression::Absurd(None, _) => true,
+        Expression::Abstraction(_, Some(_), body)
+        | Expression::TypeAbstraction(_, body)
+        | Expression::Let(_, _, body)
+        | Expression::Case(_, _, body, _, _)
+        | Expression::If(_, body, _)
+        | Expression::Fix(body) => needs_type(body),
+        Expression::Pair(left, right) => needs_type(left) || needs_type(right),
+        _ => false,
+    }
+}
+
+/// Ascribe the type (if it is known) to the expression, if it needs one to synthesise its type. \
+/// A value that is moved out of an ascription (or annotation) keeps its type this way,
+/// so every reduction step stays well-typed for the bidirectional typechecker
+fn ascribe(expr: Expression, typ: Option<&Type>) -> Expression {
+    match typ {
+        Some(typ) if needs_type(&expr) => {
+            Expression::Ascription(Box::new(expr), Box::new(typ.clone()))
+        }
+        _ => expr,
+    }
+}
+
+/// The expression under an ascription, with the ascribed type (if it has one)
+fn ascribed(expr: &Expression) -> (&Expression, Option<&Type>) {
+    match expr {
+        Expression::Ascription(inner, typ) => (inner, Some(typ)),
+        _ => (expr, None),
+    }
+}
+
+/// Reduce the leftmost outermost redex of the expression:
+/// β for applications and lets, type β for type applications,
+/// projections of pairs, cases of injections and ascriptions that are not needed,
+/// and the PCF rules for pred, iszero, if and fix. \
+/// The branches of an if are only reduced once its condition is a boolean,
+/// otherwise the unfolding of a fixed point in them would never end. \
+/// Returns None if the expression is in normal form
+fn _step(expr: &Expression) -> Option<Expression> {
+    match expr {
+        Expression::Application(lexpr, rexpr) => match ascribed(lexpr) {
+            (Expression::Abstraction(var, annotation, body), typ) => {
+                let (domain, codomain) = match typ {
+                    Some(Type::Function(domain, codomain)) => {
+                        (Some(domain.as_ref()), Some(codomain.as_ref()))
+                    }
+                    _ => (annotation.as_deref(), None),
+                };
+                let argument = ascribe(*rexpr.clone(), domain);
+                Some(ascribe(
+                    substitution(*body.clone(), var, &argument),
+                    codomain,
+                ))
+            }
+            _ => match _step(lexpr) {
+                Some(lexpr) => Some(Expression::Application(Box::new(lexpr), rexpr.clone())),
+                None => _step(rexpr)
+                    .map(|rexpr| Expression::Application(lexpr.clone(), Box::new(rexpr))),
+            },
+        },
+        Expression::Abstraction(var, typ, body) => _step(body)
+            .map(|body| Expression::Abstraction(var.clone(), typ.clone(), Box::new(body))),
+        Expression::Let(var, bound, body) => Some(substitution(*body.clone(), var, bound)),
+        Expression::TypeAbstraction(var, body) => {
+            _step(body).map(|body| Expression::TypeAbstraction(var.clone(), Box::new(body)))
+        }
+        Expression::TypeApplication(lexpr, typ) => match ascribed(lexpr) {
+            (Expression::TypeAbstraction(var, body), ascription) => {
+                let result_type = match ascription {
+                    Some(Type::ForAll(bound, result_type)) => {
+                        Some(substitute_type(result_type, bound, typ))
+                    }
+                    _ => None,
+                };
+                Some(ascribe(
+                    type_substitution(*body.clone(), var, typ),
+                    result_type.as_ref(),
+                ))
+            }
+            _ => {
+                _step(lexpr).map(|lexpr| Expression::TypeApplication(Box::new(lexpr), typ.clone()))
+            }
+        },
+        Expression::Pair(left, right) => match _step(left) {
+            Some(left) => Some(Expression::Pair(Box::new(left), right.clone())),
+            None => _step(right).map(|right| Expression::Pair(left.clone(), Box::new(right))),
+        },
+        Expression::First(pair) | Expression::Second(pair) => match ascribed(pair) {
+            (Expression::Pair(left, right), typ) => {
+                let (left_type, right_type) = match typ {
+                    Some(Type::Product(left_type, right_type)) => {
+                        (Some(left_type.as_ref()), Some(right_type.as_ref()))
+                    }
+                    _ => (None, None),
+                };
+                match expr {
+                    Expression::First(_) => Some(ascribe(*left.clone(), left_type)),
+                    _ => Some(ascribe(*right.clone(), right_type)),
+                }
+            }
+            _ => _step(pair).map(|pair| match expr {
+                Expression::First(_) => Expression::First(Box::new(pair)),
+                _ => Expression::Second(Box::new(pair)),
+            }),
+        },
+        Expression::Left(typ, inner) => {
+            _step(inner).map(|inner| Expression::Left(typ.clone(), Box::new(inner)))
+        }
+        Expression::Right(typ, inner) => {
+            _step(inner).map(|inner| Expression::Right(typ.clone(), Box::new(inner)))
+        }
+        Expression::Case(scrutinee, left, left_branch, right, right_branch) => {
+            match ascribed(scrutinee) {
+                (
+                    injection @ (Expression::Left(annotation, inner)
+                    | Expression::Right(annotation, inner)),
+                    typ,
+                ) => {
+                    // the type of the sum is ascribed to the injection, or annotated on it
+                    let (left_type, right_type) = match typ.or(annotation.as_deref()) {
+                        Some(Type::Sum(left_type, right_type)) => {
+                            (Some(left_type.as_ref()), Some(right_type.as_ref()))
+                        }
+                        _ => (None, None),
+                    };
+                    let (var, branch, typ) = match injection {
+                        Expression::Left(_, _) => (left, left_branch, left_type),
+                        _ => (right, right_branch, right_type),
+                    };
+                    Some(substitution(
+                        *branch.clone(),
+                        var,
+                        &ascribe(*inner.clone(), typ),
+                    ))
+                }
+                _ => {
+                    let case = |scrutinee, left_branch, right_branch| {
+                        Expression::Case(
+                            Box::new(scrutinee),
+                            left.clone(),
+                            Box::new(left_branch),
+                            right.clone(),
+                            Box::new(right_branch),
+                        )
+                    };
+                    if let Some(scrutinee) = _step(scrutinee) {
+                        Some(case(scrutinee, *left_branch.clone(), *right_branch.clone()))
+                    } else if let Some(left_branch) = _step(left_branch) {
+                        Some(case(*scrutinee.clone(), left_branch, *right_branch.clone()))
+                    } else {
+                        _step(right_branch).map(|right_branch| {
+                            case(*scrutinee.clone(), *left_branch.clone(), right_branch)
+                        })
+                    }
+                }
+            }
+        }
+        // an ascription has done its work, unless the expression needs it to have a type
+        Expression::Ascription(inner, _) if !needs_type(inner) => Some(*inner.clone()),
+        Expression::Ascription(inner, typ) => match inner.as_ref() {
+            // (fix f : A) = fix (f : A -> A), so the unfolding of the fixed point keeps its type
+            Expression::Fix(function) => Some(Expression::Fix(Box::new(Expression::Ascription(
+                function.clone(),
+                Box::new(Type::Function(typ.clone(), typ.clone())),
+            )))),
+            _ => _step(inner).map(|inner| Expression::Ascription(Box::new(inner), typ.clone())),
+        },
+        Expression::Succ(inner) => _step(inner).map(|inner| Expression::Succ(Box::new(inner))),
+        Expression::Pred(inner) | Expression::IsZero(inner) => match (expr, ascribed(inner).0) {
+            (Expression::Pred(_), Expression::Zero) => Some(Expression::Zero),
+            (Expression::Pred(_), Expression::Succ(predecessor)) => Some(*predecessor.clone()),
+            (Expression::IsZero(_), Expression::Zero) => Some(Expression::True),
+            (Expression::IsZero(_), Expression::Succ(_)) => Some(Expression::False),
+            _ => _step(inner).map(|inner| match expr {
+                Expression::Pred(_) => Expression::Pred(Box::new(inner)),
+                _ => Expression::IsZero(Box::new(inner)),
+            }),
+        },
+        Expression::If(condition, then_branch, else_branch) => match ascribed(condition).0 {
+            Expression::True => Some(*then_branch.clone()),
+            Expression::False => Some(*else_branch.clone()),
+            _ => _step(condition).map(|condition| {
+                Expression::If(
+                    Box::new(condition),
+                    then_branch.clone(),
+                    else_branch.clone(),
+                )
+            }),
+        },
+        Expression::Fix(function) => match ascribed(function) {
+            // fix (λf.e) = e[f := fix (λf.e)], keeping the type of the fixed point
+            (Expression::Abstraction(var, annotation, body), typ) => {
+                let typ = match typ {
+                    Some(Type::Function(domain, _)) => Some(domain.as_ref()),
+                    _ => annotation.as_deref(),
+                };
+                let unfolded = substitution(*body.clone(), var, &ascribe(expr.clone(), typ));
+                Some(ascribe(unfolded, typ))
+            }
+            _ => _step(function).map(|function| Expression::Fix(Box::new(function))),
+        },
+        // there are no values of ⊥, so absurd never reduces itself
+        Expression::Absurd(typ, inner) => {
+            _step(inner).map(|inner| Expression::Absurd(typ.clone(), Box::new(inner)))
+        }
+        Expression::Variable(_)
+        | Expression::Unit
+        | Expression::Zero
+        | Expression::True
+        | Expression::False => None,
+    }
+}
+
+/// Reset the counters of the fresh variable names and the reduction steps, before reducing a new line
+pub(crate) fn reset_counters() {
+    CUSTOM_VARNAME_COUNTER.store(1, std::sync::atomic::Ordering::SeqCst);
+    REDUCE_COUNTER.store(1, std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Reduce the term to its normal form, one `step` at a time until there is none left,
+/// erroring after more than MAX_REDUCTIONS steps (since the counters were reset)
+pub(crate) fn normalise_with<T>(
+    term: T,
+    step: impl Fn(&T) -> Option<T>,
+) -> std::result::Result<T, ReduceError> {
+    let mut term = term;
+    while let Some(next) = step(&term) {
+        if REDUCE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst) > MAX_REDUCTIONS {
+            return Err(ReduceError::ReductionOutOfBounds);
+        }
+        term = next;
+    }
+    Ok(term)
+}
+
+/// Reduce the expression to its normal form, one leftmost outermost step at a time
+fn _reduce(expr: Expression) -> ReduceResult {
+    normalise_with(expr, _step)
+}
+
+/// Reduce the expression
+/// If there is an error, prints an error and exits the program.
+///
+/// # Arguments
+/// * `expr` - The expression to reduce
+///
+/// # Returns
+/// The reduced expression
+///
+/// # Error
+/// "Error [{err_code}] caught during reducing on line {idx}!"
+pub(super) fn reduce(expr: Expression, idx: usize) -> Expression {
+    reset_counters();
+    let reduction = _reduce(expr);
+    dbg!(&reduction);
+    match reduction {
+        Ok(expr) => expr,
+        Err(err) => {
+            eprintln!(
+                "Error [{}] caught during reducing on line {}!",
+                err,
+                idx + 1
+            );
+            exit(Failure::Reduce);
+        }
+    }
+}
+
+/// Reduce the expression, returning the normal form and the number of reduction steps it took. \
+/// If there is an error, returns it. \
+/// Only used for the JSON output, where every line is reported even if one fails
+pub(crate) fn json_reduce(
+    expr: Expression,
+    idx: usize,
+) -> std::result::Result<(Expression, usize), Diagnostic> {
+    reset_counters();
+    let reduction = _reduce(expr);
+    dbg!(&reduction);
+    match reduction {
+        // the counter starts at 1 and is incremented once for every step
+        Ok(expr) => Ok((
+            expr,
+            REDUCE_COUNTER.load(std::sync::atomic::Ordering::SeqCst) - 1,
+        )),
+        Err(err) => Err(Diagnostic {
+            failure: Failure::Reduce,
+            code: match err {
+                ReduceError::ReductionOutOfBounds => "reduction_out_of_bounds",
+            },
+            message: err.to_string(),
+            line: idx + 1,
+            column: None,
+            underline: None,
+        }),
+    }
+}
+
+/// The terms the expression reduces to, one reduction step at a time, up to its normal form
+/// If there are too many steps, prints an error and exits the program.
+///
+/// # Arguments
+/// * `expr` - The expression to reduce
+///
+/// # Returns
+/// The intermediate terms, the last one is the normal form (empty if the expression already is)
+///
+/// # Error
+/// "Error [{err_code}] caught during reducing on line {idx}!"
+pub(super) fn reduction_steps(expr: &Expression, idx: usize) -> Vec<Expression> {
+    CUSTOM_VARNAME_COUNTER.store(1, std::sync::atomic::Ordering::SeqCst);
+    let mut steps: Vec<Expression> = Vec::new();
+    while let Some(next) = _step(steps.last().unwrap_or(expr)) {
+        if steps.len() == MAX_REDUCTIONS {
+            eprintln!(
+                "Error [{}] caught during reducing on line {}!",
+                ReduceError::ReductionOutOfBounds,
+                idx + 1
+            );
+            exit(Failure::Reduce);
+        }
+        dbg!(&next);
+        steps.push(next);
+    }
+    steps
+}
+
+/// Erase the types of the expression: remove the annotations of bound variables and injections,
+/// and the type abstractions and applications, leaving the untyped term it computes with,
+/// e.g. `(ΛX.λx^X.x) [A]` erases to `λx.x`
+pub(super) fn erase(expr: &Expression) -> Expression {
+    let erased = |expr: &Expression| Box::new(erase(expr));
+    match expr {
+        Expression::Application(lexpr, rexpr) => {
+            Expression::Application(erased(lexpr), erased(rexpr))
+        }
+        Expression::Abstraction(var, _, body) => {
+            Expression::Abstraction(var.clone(), None, erased(body))
+        }
+        Expression::Variable(_)
+        | Expression::Unit
+        | Expression::Zero
+        | Expression::True
+        | Expression::False => expr.clone(),
+        Expression::Let(var, bound, body) => {
+            Expression::Let(var.clone(), erased(bound), erased(body))
+        }
+        Expression::TypeAbstraction(_, body)
+        | Expression::TypeApplication(body, _)
+        | Expression::Ascription(body, _) => erase(body),
+        Expression::Pair(left, right) => Expression::Pair(erased(left), erased(right)),
+        Expression::First(pair) => Expression::First(erased(pair)),
+        Expression::Second(pair) => Expression::Second(erased(pair)),
+        Expression::Left(_, inner) => Expression::Left(None, erased(inner)),
+        Expression::Right(_, inner) => Expression::Right(None, erased(inner)),
+        Expression::Case(scrutinee, left, left_branch, right, right_branch) => Expression::Case(
+            erased(scrutinee),
+            left.clone(),
+            erased(left_branch),
+            right.clone(),
+            erased(right_branch),
+        ),
+        Expression::Succ(inner) => Expression::Succ(erased(inner)),
+        Expression::Pred(inner) => Expression::Pred(erased(inner)),
+        Expression::IsZero(inner) => Expression::IsZero(erased(inner)),
+        Expression::If(condition, then_branch, else_branch) => {
+            Expression::If(erased(condition), erased(then_branch), erased(else_branch))
+        }
+        Expression::Fix(function) => Expression::Fix(erased(function)),
+        Expression::Absurd(_, inner) => Expression::Absurd(None, erased(inner)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::manual_parse_expression, tokenizer::manual_tokenize,
+        type_checker::manual_synthesise_type,
+    };
+
+    #[test]
+    fn fresh_names_capture_nothing() {
+        // y : Nat, y1 : Nat -> Nat ⊢ (λx^Nat.λy^Nat.y1 x) y
+        let context = vec![
+            ("y".to_string(), Type::Nat),
+            (
+                "y1".to_string(),
+                Type::Function(Box::new(Type::Nat), Box::new(Type::Nat)),
+            ),
+        ];
+        let tokens = manual_tokenize("(λx^Nat.λy^Nat.y1 x) y").unwrap();
+        let expression = manual_parse_expression(&tokens).unwrap();
+        reset_counters();
+        // renaming the bound y to y1 would capture the free y1 of the body
+        let normal_form = _reduce(expression.clone()).unwrap();
+        assert!(is_free_var(&normal_form, "y1") && is_free_var(&normal_form, "y"));
+        // so the normal form still has the type of the expression
+        assert_eq!(
+            manual_synthesise_type(&normal_form, &context),
+            manual_synthesise_type(&expression, &context)
+        );
+    }
+}
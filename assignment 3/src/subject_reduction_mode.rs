@@ -0,0 +1,61 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::{is_judgement, parse, parse_open_expression, Expression, Judgement},
+    reducer::reduction_steps,
+    tokenizer::tokenize,
+    type_checker::{subject_reduction_check, synthesise_type, type_check},
+    utils::mode_arguments,
+};
+
+/// Subject reduction mode: reduce every judgement (or annotated expression) in the file (or stdin)
+/// one step at a time, and typecheck every intermediate term against the type of the original,
+/// failing as soon as a step changes the type. \
+/// Prints every judgement followed by its reduction steps, e.g. `(λx^A.x) a : A` prints `-> a`. \
+/// `-r [file]`
+pub(super) fn subject_reduction_mode(args: Vec<String>) {
+    let arguments = mode_arguments(
+        &args,
+        "Usage: lc -r [FILE]\n\nPrint the reduction steps of every judgement in FILE, or stdin if there is none, typechecking each of them",
+        &[],
+        &[],
+    );
+    let lines = match arguments.file {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+
+    let reductions: Vec<(Judgement, Vec<Expression>)> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            let judgement = if is_judgement(&tokens) {
+                let judgement = parse(&tokens, idx);
+                type_check(&judgement, idx);
+                judgement
+            } else {
+                let (context, expression) = parse_open_expression(&tokens, idx);
+                let typ = synthesise_type(&expression, &context, idx).typ;
+                Judgement::Judgement(context, Box::new(expression), Box::new(typ))
+            };
+
+            let Judgement::Judgement(context, expression, typ) = &judgement;
+            let steps = reduction_steps(expression, idx);
+            for (step, term) in steps.iter().enumerate() {
+                let reduced =
+                    Judgement::Judgement(context.clone(), Box::new(term.clone()), typ.clone());
+                subject_reduction_check(&reduced, step + 1, idx);
+            }
+            (judgement, steps)
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the reductions
+    for (judgement, steps) in reductions {
+        println!("{}", judgement);
+        for step in steps {
+            println!("-> {}", step);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::{
+    line_reader::{read_lines_from_file, read_lines_from_terminal},
+    parser::{is_judgement, parse, parse_open_expression, Judgement},
+    tokenizer::tokenize,
+    type_checker::{synthesise_type, type_check},
+    utils::mode_arguments,
+};
+
+/// Synthesis mode: print every annotated expression in the file (or stdin) with its type,
+/// e.g. `λx^A.λy^(A -> B).y x` prints `λx^A.λy^(A -> B).y x : A -> (A -> B) -> B`. \
+/// The free variables get their types from a context before the turnstile, e.g. `x : A ⊢ x` prints `x : A ⊢ x : A`. \
+/// A line that already is a judgement is typechecked like in normal mode. \
+/// `-s [file]`
+pub(super) fn synth_mode(args: Vec<String>) {
+    let arguments = mode_arguments(
+        &args,
+        "Usage: lc -s [FILE]\n\nPrint every annotated expression (or judgement) in FILE, or stdin if there is none, with its type",
+        &[],
+        &[],
+    );
+    let lines = match arguments.file {
+        Some(filename) => read_lines_from_file(&filename),
+        None => read_lines_from_terminal(),
+    };
+    synthesise_lines(lines);
+}
+
+/// Print every line with its type, or typecheck it if it is a judgement (see synthesis mode)
+pub(super) fn synthesise_lines(lines: Vec<(usize, String)>) {
+    let judgements: Vec<Judgement> = lines
+        .into_iter()
+        .map(|(idx, line)| {
+            let tokens = tokenize(&line, idx);
+            if is_judgement(&tokens) {
+                let judgement = parse(&tokens, idx);
+                type_check(&judgement, idx);
+                judgement
+            } else {
+                let (context, expression) = parse_open_expression(&tokens, idx);
+                let typ = synthesise_type(&expression, &context, idx).typ;
+                Judgement::Judgement(context, Box::new(expression), Box::new(typ))
+            }
+        })
+        .collect();
+    // We can only get here if we have 0 errors, so print the judgements
+    for judgement in judgements {
+        println!("{}", judgement);
+    }
+}
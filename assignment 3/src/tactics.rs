@@ -0,0 +1,286 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+// Import handy dbg! macro (shadowing std::dbg! macro)
+use crate::{
+    dbg,
+    parser::{Context, Expression, Judgement, Type},
+    type_checker::{manual_synthesise_type, manual_type_check},
+    types::alpha_equivalent,
+};
+
+use std::fmt::{Display, Formatter, Result};
+
+/// A tactic, which refines the current goal into zero or more new goals
+#[derive(Debug, Clone)]
+pub(crate) enum Tactic {
+    /// intro <Variable>: prove A -> B by proving B with the variable of type A
+    Intro(String),
+    /// exact <Expression>: prove the goal with a term that checks against it
+    Exact(Expression),
+    /// apply <Expression>: prove B with a function f : A1 -> ... -> An -> B, leaving A1, ..., An as goals
+    Apply(Expression),
+    /// split: prove A * B by proving A and B
+    Split,
+    /// left: prove A + B by proving A
+    Left,
+    /// right: prove A + B by proving B
+    Right,
+}
+
+#[derive(Debug)]
+pub(crate) enum TacticError {
+    /// There is no goal left to apply the tactic to
+    NoGoals,
+    /// intro on a goal that is not a function type
+    NotAFunction(Type),
+    /// split on a goal that is not a product
+    NotAProduct(Type),
+    /// left or right on a goal that is not a sum
+    NotASum(Type),
+    /// The function, its type, and the goal that none of its codomains is
+    CannotApply(Box<Expression>, Type, Type),
+    /// The term of exact or apply has no (or the wrong) type, the typechecker printed why
+    Rejected,
+}
+
+impl Display for TacticError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            TacticError::NoGoals => write!(f, "No goals left, the proof is complete"),
+            TacticError::NotAFunction(goal) => {
+                write!(f, "Cannot intro, the goal {} is not a function type", goal)
+            }
+            TacticError::NotAProduct(goal) => {
+                write!(f, "Cannot split, the goal {} is not a product", goal)
+            }
+            TacticError::NotASum(goal) => write!(f, "The goal {} is not a sum", goal),
+            TacticError::CannotApply(function, typ, goal) => write!(
+                f,
+                "Cannot apply '{}' of type {}, it does not conclude the goal {}",
+                function, typ, goal
+            ),
+            TacticError::Rejected => write!(f, "The term was rejected by the typechecker"),
+        }
+    }
+}
+
+type TacticResult<T> = std::result::Result<T, TacticError>;
+
+/// A goal: a term of the type has to be found, in which the variables of the context can be used
+#[derive(Debug, Clone)]
+struct Goal {
+    context: Context,
+    target: Type,
+}
+
+/// How a goal was proven, with the indices of the goals it left
+#[derive(Debug, Clone)]
+enum Refinement {
+    /// λx^A.?body
+    Intro(String, Type, usize),
+    /// The term itself
+    Exact(Expression),
+    /// f ?arg1 ... ?argn
+    Apply(Expression, Vec<usize>),
+    /// (?left, ?right)
+    Split(usize, usize),
+    /// inl^(A + B) ?inner
+    Left(Type, usize),
+    /// inr^(A + B) ?inner
+    Right(Type, usize),
+}
+
+/// The state of an interactive proof: every goal that was stated, how the proven ones were proven,
+/// and a stack of the open goals (the current goal first)
+#[derive(Debug, Clone)]
+pub(crate) struct ProofState {
+    goals: Vec<Goal>,
+    refinements: Vec<Option<Refinement>>,
+    open: Vec<usize>,
+}
+
+impl ProofState {
+    /// Start a proof of the type, in the empty context
+    pub(crate) fn new(typ: Type) -> Self {
+        ProofState {
+            goals: vec![Goal {
+                context: Context::new(),
+                target: typ,
+            }],
+            refinements: vec![None],
+            open: vec![0],
+        }
+    }
+
+    /// Whether every goal is proven
+    pub(crate) fn is_complete(&self) -> bool {
+        self.open.is_empty()
+    }
+
+    /// Add a goal, returns its index
+    fn add_goal(&mut self, context: Context, target: Type) -> usize {
+        self.goals.push(Goal { context, target });
+        self.refinements.push(None);
+        self.goals.len() - 1
+    }
+
+    /// Apply the tactic to the current goal. The new goals (if any) come first, in order
+    pub(crate) fn apply(&mut self, tactic: &Tactic) -> TacticResult<()> {
+        let Some(&current) = self.open.first() else {
+            return Err(TacticError::NoGoals);
+        };
+        let Goal { context, target } = self.goals[current].clone();
+        let (refinement, new_goals) = match tactic {
+            Tactic::Intro(var) => {
+                let Type::Function(domain, codomain) = &target else {
+                    return Err(TacticError::NotAFunction(target));
+                };
+                let mut body_context = context.clone();
+                body_context.push((var.clone(), *domain.clone()));
+                let body = self.add_goal(body_context, *codomain.clone());
+                (
+                    Refinement::Intro(var.clone(), *domain.clone(), body),
+                    vec![body],
+                )
+            }
+            Tactic::Exact(expr) => {
+                let judgement = Judgement::Judgement(
+                    context.clone(),
+                    Box::new(expr.clone()),
+                    Box::new(target.clone()),
+                );
+                if !manual_type_check(&judgement) {
+                    return Err(TacticError::Rejected);
+                }
+                (Refinement::Exact(expr.clone()), Vec::new())
+            }
+            Tactic::Apply(function) => {
+                let Some(typ) = manual_synthesise_type(function, &context) else {
+                    return Err(TacticError::Rejected);
+                };
+                // the fewest arguments after which the function concludes the goal
+                let mut arguments = Vec::new();
+                let mut conclusion = &typ;
+                while !alpha_equivalent(conclusion, &target) {
+                    let Type::Function(domain, codomain) = conclusion else {
+                        return Err(TacticError::CannotApply(
+                            Box::new(function.clone()),
+                            typ,
+                            target,
+                        ));
+                    };
+                    arguments.push(*domain.clone());
+                    conclusion = codomain;
+                }
+                let arguments = arguments
+                    .into_iter()
+                    .map(|argument| self.add_goal(context.clone(), argument))
+                    .collect::<Vec<usize>>();
+                (
+                    Refinement::Apply(function.clone(), arguments.clone()),
+                    arguments,
+                )
+            }
+            Tactic::Split => {
+                let Type::Product(left_type, right_type) = &target else {
+                    return Err(TacticError::NotAProduct(target));
+                };
+                let left = self.add_goal(context.clone(), *left_type.clone());
+                let right = self.add_goal(context.clone(), *right_type.clone());
+                (Refinement::Split(left, right), vec![left, right])
+            }
+            Tactic::Left | Tactic::Right => {
+                let Type::Sum(left_type, right_type) = &target else {
+                    return Err(TacticError::NotASum(target));
+                };
+                match tactic {
+                    Tactic::Left => {
+                        let inner = self.add_goal(context.clone(), *left_type.clone());
+                        (Refinement::Left(target.clone(), inner), vec![inner])
+                    }
+                    _ => {
+                        let inner = self.add_goal(context.clone(), *right_type.clone());
+                        (Refinement::Right(target.clone(), inner), vec![inner])
+                    }
+                }
+            }
+        };
+        dbg!(&refinement);
+        self.refinements[current] = Some(refinement);
+        self.open.splice(0..1, new_goals);
+        Ok(())
+    }
+
+    /// The term of the goal, where the open goals are holes `?1`, `?2`, ... (numbered like the goal stack)
+    fn term_of(&self, goal: usize) -> Expression {
+        let term = |goal: &usize| Box::new(self.term_of(*goal));
+        match &self.refinements[goal] {
+            None => {
+                let position = self.open.iter().position(|open| *open == goal).unwrap_or(0);
+                Expression::Variable(format!("?{}", position + 1))
+            }
+            Some(Refinement::Intro(var, typ, body)) => {
+                Expression::Abstraction(var.clone(), Some(Box::new(typ.clone())), term(body))
+            }
+            Some(Refinement::Exact(expr)) => expr.clone(),
+            Some(Refinement::Apply(function, arguments)) => arguments
+                .iter()
+                .fold(function.clone(), |function, argument| {
+                    Expression::Application(Box::new(function), term(argument))
+                }),
+            Some(Refinement::Split(left, right)) => Expression::Pair(term(left), term(right)),
+            Some(Refinement::Left(typ, inner)) => {
+                Expression::Left(Some(Box::new(typ.clone())), term(inner))
+            }
+            Some(Refinement::Right(typ, inner)) => {
+                Expression::Right(Some(Box::new(typ.clone())), term(inner))
+            }
+        }
+    }
+
+    /// The term constructed so far, with holes for the open goals
+    pub(crate) fn term(&self) -> Expression {
+        self.term_of(0)
+    }
+
+    /// The judgement of the constructed term and the type that was stated
+    pub(crate) fn judgement(&self) -> Judgement {
+        Judgement::Judgement(
+            Context::new(),
+            Box::new(self.term()),
+            Box::new(self.goals[0].target.clone()),
+        )
+    }
+}
+
+/// Print the current goal with its context, and the other open goals below it, e.g.
+/// ```text
+/// Goal 1 of 2:
+///   x : A
+///   ---------
+///   B
+/// Goal 2: A
+/// ```
+impl Display for ProofState {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if self.open.is_empty() {
+            return write!(f, "No goals left");
+        }
+        for (i, goal) in self.open.iter().enumerate() {
+            let Goal { context, target } = &self.goals[*goal];
+            if i > 0 {
+                writeln!(f)?;
+                write!(f, "Goal {}: {}", i + 1, target)?;
+                continue;
+            }
+            writeln!(f, "Goal 1 of {}:", self.open.len())?;
+            for (var, typ) in context {
+                writeln!(f, "  {} : {}", var, typ)?;
+            }
+            writeln!(f, "  ---------")?;
+            write!(f, "  {}", target)?;
+        }
+        Ok(())
+    }
+}
@@ -3,12 +3,24 @@
 
 // Import handy dbg! macro (shadowing std::dbg! macro)
 use crate::dbg;
+use crate::json::Diagnostic;
+use crate::utils::{exit, Failure};
 use std::fmt::{Display, Formatter, Result};
+use std::iter::{Enumerate, Peekable};
+use std::str::Chars;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub(crate) enum Token {
     /// an abstraction with a bound variable
     Lambda(String),
+    /// a type abstraction with a bound type variable, `ΛX` or `/\X`
+    TypeLambda(String),
+    /// universal quantifier of a type, `∀` or `forall` (or a dependent product `Π`)
+    Forall,
+    /// left bracket, around the type of a type application
+    LBracket,
+    /// right bracket
+    RBracket,
     /// left parenthesis
     LParen,
     /// right parenthesis
@@ -25,6 +37,64 @@ pub(crate) enum Token {
     Colon,
     /// Dot
     Dot,
+    /// let keyword
+    Let,
+    /// in keyword
+    In,
+    /// Equals sign
+    Equals,
+    /// Turnstile, `|-` or `⊢`
+    Turnstile,
+    /// Comma
+    Comma,
+    /// Product type, `*` (or conjunction, `∧`)
+    Star,
+    /// Sum type, `+` (or disjunction, `∨`)
+    Plus,
+    /// The empty type, falsity `⊥`
+    Bottom,
+    /// Negation, `¬`, short for a function into `⊥`
+    Not,
+    /// fst keyword, first projection of a pair
+    Fst,
+    /// snd keyword, second projection of a pair
+    Snd,
+    /// inl keyword, left injection into a sum
+    Inl,
+    /// inr keyword, right injection into a sum
+    Inr,
+    /// case keyword
+    Case,
+    /// of keyword
+    Of,
+    /// Bar, between the branches of a case
+    Bar,
+    /// Fat arrow, `=>`, between the pattern and the body of a branch
+    FatArrow,
+    /// The sort of kinds, `□`, in the calculus of constructions
+    Square,
+    /// A natural number literal, e.g. `0` or `3`
+    Numeral(usize),
+    /// succ keyword, successor of a natural number
+    Succ,
+    /// pred keyword, predecessor of a natural number
+    Pred,
+    /// iszero keyword, test for zero
+    IsZero,
+    /// true keyword
+    True,
+    /// false keyword
+    False,
+    /// if keyword
+    If,
+    /// then keyword
+    Then,
+    /// else keyword
+    Else,
+    /// fix keyword, fixed point of a function
+    Fix,
+    /// absurd keyword, elimination of the empty type
+    Absurd,
 }
 
 #[allow(unused)]
@@ -38,6 +108,8 @@ pub(super) enum LexError {
     EmptyLambdaVariable(usize),
     InvalidArrow(usize),
     TrailingDot(usize),
+    UnterminatedComment(usize),
+    InvalidNumeral(usize),
 }
 
 impl Display for LexError {
@@ -55,25 +127,197 @@ impl Display for LexError {
             }
             LexError::InvalidArrow(i) => write!(f, "Invalid type arrow at pos: {}", i),
             LexError::TrailingDot(i) => write!(f, "Trailing dot at pos: {}", i),
+            LexError::UnterminatedComment(i) => {
+                write!(f, "Unterminated block comment starting at pos: {}", i)
+            }
+            LexError::InvalidNumeral(i) => write!(f, "Invalid numeral at pos: {}", i),
+        }
+    }
+}
+
+impl LexError {
+    /// The identifier of the error in the JSON output
+    fn code(&self) -> &'static str {
+        match self {
+            LexError::EmptyVariableName(_) => "empty_variable_name",
+            LexError::InvalidCharacter(_, _) => "invalid_character",
+            LexError::InvalidExpression(_) => "invalid_expression",
+            LexError::InvalidVariableName(_) => "invalid_variable_name",
+            LexError::InvalidLambdaVariableChar(_, _) => "invalid_lambda_variable_char",
+            LexError::EmptyLambdaVariable(_) => "empty_lambda_variable",
+            LexError::InvalidArrow(_) => "invalid_arrow",
+            LexError::TrailingDot(_) => "trailing_dot",
+            LexError::UnterminatedComment(_) => "unterminated_comment",
+            LexError::InvalidNumeral(_) => "invalid_numeral",
+        }
+    }
+
+    /// The position (1-based character index) the error occurred at
+    fn pos_mut(&mut self) -> &mut usize {
+        match self {
+            LexError::EmptyVariableName(i)
+            | LexError::InvalidCharacter(_, i)
+            | LexError::InvalidExpression(i)
+            | LexError::InvalidVariableName(i)
+            | LexError::InvalidLambdaVariableChar(_, i)
+            | LexError::EmptyLambdaVariable(i)
+            | LexError::InvalidArrow(i)
+            | LexError::TrailingDot(i)
+            | LexError::UnterminatedComment(i)
+            | LexError::InvalidNumeral(i) => i,
         }
     }
 }
 
 type LexResult<T> = std::result::Result<T, LexError>;
 
+type CharStream<'a> = Peekable<Enumerate<Chars<'a>>>;
+
+/// Where a token starts: the number of lines before its line, and its (1-based) column on that line
+pub(crate) type Position = (usize, usize);
+
+/// Read the type variable bound by a type abstraction, e.g. the `X` of `ΛX. e`. \
+/// The `Λ` or `/\` has already been consumed, `start` is its index
+fn type_lambda_variable(chars: &mut CharStream, start: usize) -> LexResult<String> {
+    let mut varname = String::new();
+    loop {
+        // a comment ends the variable name, or is skipped if the name has not started yet
+        if comment_ahead(chars) {
+            if !varname.is_empty() {
+                break;
+            }
+            skip_comment(chars)?;
+            continue;
+        }
+        let Some((idx, c)) = chars.peek() else {
+            break;
+        };
+        match c {
+            // like for lambdas, these always signify the end of the variable name
+            '.' | '(' | '\\' | 'λ' | 'Λ' | '/' | ':' => {
+                if varname.is_empty() {
+                    return Err(LexError::EmptyVariableName(*idx + 1));
+                }
+                break;
+            }
+            c if c.is_whitespace() => {
+                if !varname.is_empty() {
+                    break;
+                }
+                chars.next();
+            }
+            // The first character of a type variable must be alphabetic, ascii and uppercase
+            c if c.is_ascii_uppercase() => {
+                varname.push(chars.next().unwrap().1);
+            }
+            c if c.is_alphanumeric() => {
+                if varname.is_empty() {
+                    return Err(LexError::InvalidVariableName(*idx + 1));
+                }
+                varname.push(chars.next().unwrap().1);
+            }
+            _ => {
+                let next = chars.next().unwrap();
+                return Err(LexError::InvalidLambdaVariableChar(next.1, next.0 + 1));
+            }
+        }
+    }
+    if varname.is_empty() {
+        return Err(LexError::EmptyLambdaVariable(start + 1));
+    }
+    Ok(varname)
+}
+
+/// Skip a line comment (`#` or `--`), up to (but not including) the next newline
+fn skip_line_comment(chars: &mut CharStream) {
+    while let Some((_, c)) = chars.peek() {
+        if *c == '\n' {
+            break;
+        }
+        chars.next();
+    }
+}
+
+/// Skip a block comment `{- ... -}`, block comments can be nested. \
+/// The opening '{' has already been consumed, `start` is its index
+fn skip_block_comment(chars: &mut CharStream, start: usize) -> LexResult<()> {
+    // consume the '-' of the opening delimiter
+    chars.next();
+    let mut depth = 1;
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '{' if matches!(chars.peek(), Some((_, '-'))) => {
+                chars.next();
+                depth += 1;
+            }
+            '-' if matches!(chars.peek(), Some((_, '}'))) => {
+                chars.next();
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(LexError::UnterminatedComment(start + 1))
+}
+
+/// Whether a comment (`#`, `--` or `{-`) starts at the next character
+fn comment_ahead(chars: &CharStream) -> bool {
+    let mut ahead = chars.clone().map(|(_, c)| c);
+    matches!(
+        (ahead.next(), ahead.next()),
+        (Some('#'), _) | (Some('-'), Some('-')) | (Some('{'), Some('-'))
+    )
+}
+
+/// Skip the comment that starts at the next character (see `comment_ahead`)
+fn skip_comment(chars: &mut CharStream) -> LexResult<()> {
+    match chars.next() {
+        Some((start, '{')) => skip_block_comment(chars, start),
+        _ => {
+            skip_line_comment(chars);
+            Ok(())
+        }
+    }
+}
+
 fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
+    _tokenize_with_positions(input).map(|(tokens, _)| tokens)
+}
+
+/// Tokenize the input, with the position (1-based character index) every token starts at
+fn _tokenize_with_positions(input: &str) -> LexResult<(Vec<Token>, Vec<usize>)> {
     let mut tokens = Vec::with_capacity(input.len());
+    let mut positions = Vec::with_capacity(input.len());
+    let mut start = 1;
     let mut chars = input.chars().enumerate().peekable();
 
     while let Some((idx, c)) = chars.next() {
+        // the tokens pushed since the previous character started at that character
+        positions.resize(tokens.len(), start);
+        start = idx + 1;
         match c {
             '\\' | 'λ' => {
                 // retrieve lambda variable
                 let mut varname = String::new();
-                while let Some((idx, c)) = chars.peek() {
+                loop {
+                    // a comment ends the variable name, or is skipped if the name has not started yet
+                    if comment_ahead(&chars) {
+                        if !varname.is_empty() {
+                            break;
+                        }
+                        skip_comment(&mut chars)?;
+                        continue;
+                    }
+                    let Some((idx, c)) = chars.peek() else {
+                        break;
+                    };
                     match c {
                         // a dot, a lambda and a left parenthesis always signify the end of the variable name
-                        '.' | '(' | '\\' | 'λ' | '^' => {
+                        // (a colon too, for the typed binders of the calculus of constructions)
+                        '.' | '(' | '\\' | 'λ' | 'Λ' | '/' | '^' | ':' => {
                             if varname.is_empty() {
                                 return Err(LexError::EmptyVariableName(*idx + 1));
                             }
@@ -114,10 +358,50 @@ fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
                 tokens.push(Token::Lambda(varname));
             }
 
+            'Λ' => tokens.push(Token::TypeLambda(type_lambda_variable(&mut chars, idx)?)),
+            '/' => {
+                if let Some((_, '\\')) = chars.peek() {
+                    chars.next();
+                    tokens.push(Token::TypeLambda(type_lambda_variable(&mut chars, idx)?));
+                } else {
+                    return Err(LexError::InvalidCharacter(c, idx + 1));
+                }
+            }
+            '∀' | 'Π' => tokens.push(Token::Forall),
+            '□' => tokens.push(Token::Square),
+
             '(' => tokens.push(Token::LParen),
             ')' => tokens.push(Token::RParen),
+            '[' => tokens.push(Token::LBracket),
+            ']' => tokens.push(Token::RBracket),
             '^' => tokens.push(Token::Hat),
             ':' => tokens.push(Token::Colon),
+            '=' => {
+                if let Some((_, '>')) = chars.peek() {
+                    chars.next();
+                    tokens.push(Token::FatArrow);
+                } else {
+                    tokens.push(Token::Equals);
+                }
+            }
+            '*' | '∧' => tokens.push(Token::Star),
+            '+' | '∨' => tokens.push(Token::Plus),
+            '⊥' => tokens.push(Token::Bottom),
+            '¬' => tokens.push(Token::Not),
+            ',' => tokens.push(Token::Comma),
+            '⊢' => tokens.push(Token::Turnstile),
+            '|' => {
+                if let Some((_, '-')) = chars.peek() {
+                    chars.next();
+                    tokens.push(Token::Turnstile);
+                } else {
+                    tokens.push(Token::Bar);
+                }
+            }
+            // comments are ignored
+            '#' => skip_line_comment(&mut chars),
+            '-' if matches!(chars.peek(), Some((_, '-'))) => skip_line_comment(&mut chars),
+            '{' if matches!(chars.peek(), Some((_, '-'))) => skip_block_comment(&mut chars, idx)?,
             '-' => {
                 if let Some((_, '>')) = chars.peek() {
                     chars.next();
@@ -128,6 +412,7 @@ fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
             }
             '.' => {
                 // check if there is something after the dot
+                let mut found = false;
                 while let Some((_, c)) = chars.peek() {
                     match c {
                         c if c.is_whitespace() => {
@@ -139,10 +424,14 @@ fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
                         ':' => return Err(LexError::TrailingDot(idx + 1)),
                         _ => {
                             tokens.push(Token::Dot);
+                            found = true;
                             break;
                         }
                     }
                 }
+                if !found {
+                    return Err(LexError::TrailingDot(idx + 1));
+                }
             }
 
             // a variable name must start with an alphabetic ascii character
@@ -155,10 +444,44 @@ fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
                         break;
                     }
                 }
-                if c.is_ascii_lowercase() {
-                    tokens.push(Token::LVariable(varname));
-                } else {
-                    tokens.push(Token::UVariable(varname));
+                match varname.as_str() {
+                    "let" => tokens.push(Token::Let),
+                    "in" => tokens.push(Token::In),
+                    "forall" => tokens.push(Token::Forall),
+                    "fst" => tokens.push(Token::Fst),
+                    "snd" => tokens.push(Token::Snd),
+                    "inl" => tokens.push(Token::Inl),
+                    "inr" => tokens.push(Token::Inr),
+                    "case" => tokens.push(Token::Case),
+                    "of" => tokens.push(Token::Of),
+                    "succ" => tokens.push(Token::Succ),
+                    "pred" => tokens.push(Token::Pred),
+                    "iszero" => tokens.push(Token::IsZero),
+                    "true" => tokens.push(Token::True),
+                    "false" => tokens.push(Token::False),
+                    "if" => tokens.push(Token::If),
+                    "then" => tokens.push(Token::Then),
+                    "else" => tokens.push(Token::Else),
+                    "fix" => tokens.push(Token::Fix),
+                    "absurd" => tokens.push(Token::Absurd),
+                    _ if c.is_ascii_lowercase() => tokens.push(Token::LVariable(varname)),
+                    _ => tokens.push(Token::UVariable(varname)),
+                }
+            }
+
+            // a natural number literal, e.g. `3` (short for `succ (succ (succ 0))`)
+            c if c.is_ascii_digit() => {
+                let mut digits = String::from(c);
+                while let Some((_, c)) = chars.peek() {
+                    if c.is_alphanumeric() {
+                        digits.push(chars.next().unwrap().1);
+                    } else {
+                        break;
+                    }
+                }
+                match digits.parse() {
+                    Ok(n) => tokens.push(Token::Numeral(n)),
+                    Err(_) => return Err(LexError::InvalidNumeral(idx + 1)),
                 }
             }
 
@@ -169,7 +492,32 @@ fn _tokenize(input: &str) -> LexResult<Vec<Token>> {
             _ => return Err(LexError::InvalidCharacter(c, idx + 1)),
         }
     }
-    Ok(tokens)
+    positions.resize(tokens.len(), start);
+    Ok((tokens, positions))
+}
+
+/// Errors are positioned relative to the start of the input, which can span multiple lines
+/// (e.g. a block comment that is continued on the next line). \
+/// Rewrites the position of the error to the column on its own line,
+/// and returns the number of lines before that line
+fn relocate(input: &str, err_code: &mut LexError) -> usize {
+    let pos = err_code.pos_mut();
+    let (line, column) = line_and_column(input, *pos);
+    *pos = column;
+    line
+}
+
+/// The number of lines before the (1-based) position in the input, and its column on its own line
+fn line_and_column(input: &str, pos: usize) -> (usize, usize) {
+    let mut line = 0;
+    let mut column = pos;
+    for (i, c) in input.chars().enumerate().take(pos.saturating_sub(1)) {
+        if c == '\n' {
+            line += 1;
+            column = pos - (i + 1);
+        }
+    }
+    (line, column)
 }
 
 /// Parse the given string into a vector of tokens
@@ -189,19 +537,51 @@ pub(crate) fn tokenize(input: &str, idx: usize) -> Vec<Token> {
     dbg!(&tokens);
     match tokens {
         // If error in a token, print error and exit
-        Err(err_code) => {
+        Err(mut err_code) => {
+            // the input can span multiple physical lines, so point at the line the error is on
+            let line = relocate(input, &mut err_code);
             eprintln!(
                 "Invalid expression [{}] caught during tokenizing on line {}!",
                 err_code,
-                idx + 1
+                idx + line + 1
             );
-            std::process::exit(1);
+            exit(Failure::Lex);
         }
         // Else: parse the tokens
         Ok(tokens) => tokens,
     }
 }
 
+/// Parse the given string into a vector of tokens, with the line offset and column every token starts at. \
+/// If the given string is not a valid expression, returns the error with its line and column. \
+/// Only used for the JSON output, where every line is reported even if one fails
+pub(crate) fn json_tokenize(
+    input: &str,
+    idx: usize,
+) -> std::result::Result<(Vec<Token>, Vec<Position>), Diagnostic> {
+    let tokens = _tokenize_with_positions(input);
+    dbg!(&tokens);
+    tokens
+        .map(|(tokens, positions)| {
+            let positions = positions
+                .into_iter()
+                .map(|pos| line_and_column(input, pos))
+                .collect();
+            (tokens, positions)
+        })
+        .map_err(|mut err_code| {
+            let line = relocate(input, &mut err_code);
+            Diagnostic {
+                failure: Failure::Lex,
+                code: err_code.code(),
+                line: idx + line + 1,
+                column: Some(*err_code.pos_mut()),
+                message: err_code.to_string(),
+                underline: None,
+            }
+        })
+}
+
 /// Parse the given string into a vector of tokens
 /// Only used for benchmarking
 /// Unwraps the result, so panics if there is an error, for ultimate speed
@@ -230,3 +610,117 @@ pub(crate) fn manual_tokenize(input: &str) -> Option<Vec<Token>> {
         Ok(tokens) => tokens.into(),
     }
 }
+
+/// Whether the given input contains nothing but whitespace and comments
+pub(crate) fn is_blank(input: &str) -> bool {
+    matches!(_tokenize(input), Ok(tokens) if tokens.is_empty())
+}
+
+/// Whether the given input is unfinished and continues on the next line:
+/// a block comment, a parenthesis or a bracket is still open,
+/// or it ends with a (type) lambda, quantifier, dot, type hat, arrow, colon, turnstile, comma,
+/// type operator, projection, injection, PCF operator or an unfinished let, case or if
+pub(crate) fn is_incomplete(input: &str) -> bool {
+    match _tokenize(input) {
+        Err(LexError::UnterminatedComment(_)) | Err(LexError::TrailingDot(_)) => true,
+        Err(_) => false,
+        Ok(tokens) => {
+            let mut paren_count = 0;
+            for token in &tokens {
+                match token {
+                    Token::LParen | Token::LBracket => paren_count += 1,
+                    Token::RParen | Token::RBracket => paren_count -= 1,
+                    _ => {}
+                }
+            }
+            if paren_count > 0 {
+                return true;
+            }
+            // a binder, type hat, arrow, colon, turnstile, comma, operator, let, case or if still needs its right hand side
+            // (but not `*`, which is also the sort of types in the calculus of constructions)
+            matches!(
+                tokens.last(),
+                Some(
+                    Token::Lambda(_)
+                        | Token::TypeLambda(_)
+                        | Token::Forall
+                        | Token::Dot
+                        | Token::Hat
+                        | Token::Arrow
+                        | Token::Colon
+                        | Token::Let
+                        | Token::Equals
+                        | Token::In
+                        | Token::Turnstile
+                        | Token::Comma
+                        | Token::Plus
+                        | Token::Fst
+                        | Token::Snd
+                        | Token::Inl
+                        | Token::Inr
+                        | Token::Case
+                        | Token::Of
+                        | Token::Bar
+                        | Token::FatArrow
+                        | Token::Succ
+                        | Token::Pred
+                        | Token::IsZero
+                        | Token::Fix
+                        | Token::Absurd
+                        | Token::Not
+                        | Token::If
+                        | Token::Then
+                        | Token::Else
+                )
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lambda(var: &str) -> Token {
+        Token::Lambda(var.to_string())
+    }
+
+    fn variable(var: &str) -> Token {
+        Token::LVariable(var.to_string())
+    }
+
+    #[test]
+    fn line_comment_after_binder() {
+        assert_eq!(_tokenize("\\x#c"), Ok(vec![lambda("x")]));
+        assert_eq!(_tokenize("\\x--c"), Ok(vec![lambda("x")]));
+        assert_eq!(
+            _tokenize("λx # c\n.x"),
+            Ok(vec![lambda("x"), Token::Dot, variable("x")])
+        );
+    }
+
+    #[test]
+    fn block_comment_after_binder() {
+        assert_eq!(
+            _tokenize("\\x{- c -} x"),
+            Ok(vec![lambda("x"), variable("x")])
+        );
+        assert_eq!(
+            _tokenize("\\x{- c -}.x"),
+            Ok(vec![lambda("x"), Token::Dot, variable("x")])
+        );
+    }
+
+    #[test]
+    fn block_comment_before_binder_name() {
+        assert_eq!(
+            _tokenize("\\{- c -} x.x"),
+            Ok(vec![lambda("x"), Token::Dot, variable("x")])
+        );
+    }
+
+    #[test]
+    fn line_comment_after_variable() {
+        assert_eq!(_tokenize("a#c"), Ok(vec![variable("a")]));
+    }
+}
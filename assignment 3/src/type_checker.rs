@@ -5,138 +5,626 @@
 
 use crate::{
     dbg,
-    parser::{Expression, Judgement, Type},
+    derivation::{Derivation, Rule},
+    json::{Diagnostic, Underline},
+    parser::{Context, Expression, Judgement, Type},
+    printer::{type_diff, underline},
+    types::{alpha_equivalent, is_free_in, substitute_type},
+    utils::{exit, Failure},
 };
 
-use std::collections::HashSet;
 use std::fmt::{Display, Formatter, Result};
 
+/// A type error, with the expression it is at (part of the expression that is checked)
 #[derive(Debug)]
-enum TypeCheckError {
-    UnknownType(String),
-    MismatchedTypes(String),
+enum TypeCheckError<'a> {
+    /// A variable that is not bound by any abstraction or the context, so its type is unknown
+    UnboundVariable(&'a Expression),
+    /// The expected type, the type that was found, and the expression it was found at
+    MismatchedTypes(Type, Type, &'a Expression),
+    /// The type the function expects, the type of the argument, and the argument
+    MismatchedArgument(Type, Type, &'a Expression),
+    /// The type of the judgement, and the type its expression has
+    MismatchedJudgement(Type, Type),
+    /// The type of the expression that is applied, but is not a function
+    NotAFunction(Type, &'a Expression),
+    /// A variable that is bound without a type, and its abstraction
+    MissingAnnotation(String, &'a Expression),
+    /// The type of the expression that is applied to a type, but is not polymorphic
+    NotPolymorphic(Type, &'a Expression),
+    /// A type variable that is abstracted over, but also occurs free in the context
+    EscapingTypeVariable(String, &'a Expression),
+    /// The type of the expression that is projected, but is not a product
+    NotAProduct(Type, &'a Expression),
+    /// The type of the expression that is cased on (or injected into), but is not a sum
+    NotASum(Type, &'a Expression),
+    /// An injection without the type of its sum
+    MissingSumType(&'a Expression),
+    /// An absurd without the type it concludes
+    MissingAbsurdType(&'a Expression),
+    /// The expected type, and the unannotated abstraction or injection that cannot have it
+    CannotHaveType(Type, &'a Expression),
+    /// The type of the expression that a fixed point is taken of, but is not of the form A -> A
+    NoFixedPoint(Type, &'a Expression),
 }
 
-impl Display for TypeCheckError {
+impl Display for TypeCheckError<'_> {
     fn fmt(&self, f: &mut Formatter) -> Result {
         match self {
-            TypeCheckError::UnknownType(t) => write!(f, "Unknown type: {}", t),
-            TypeCheckError::MismatchedTypes(t) => {
-                write!(f, "Mismatched types, unknown type: {}", t)
+            TypeCheckError::UnboundVariable(var) => write!(f, "Unbound variable: {}", var),
+            TypeCheckError::MismatchedTypes(expected, found, expr) => {
+                write!(
+                    f,
+                    "Mismatched types, expected {}, found {} at '{}'",
+                    expected, found, expr
+                )
+            }
+            TypeCheckError::MismatchedArgument(expected, found, expr) => {
+                write!(
+                    f,
+                    "Mismatched argument, the function expects {}, found {} at '{}'",
+                    expected, found, expr
+                )
+            }
+            TypeCheckError::MismatchedJudgement(expected, found) => {
+                write!(
+                    f,
+                    "Mismatched types, the judgement has type {}, but the expression has type {}",
+                    expected, found
+                )
+            }
+            TypeCheckError::NotAFunction(typ, expr) => {
+                write!(
+                    f,
+                    "Expected a function, found {} at '{}', which cannot be applied",
+                    typ, expr
+                )
+            }
+            TypeCheckError::MissingAnnotation(var, _) => {
+                write!(
+                    f,
+                    "Missing type of bound variable: {} (ascribe a type to the abstraction, or use -i to infer it)",
+                    var
+                )
+            }
+            TypeCheckError::NotPolymorphic(typ, expr) => {
+                write!(
+                    f,
+                    "Expected a polymorphic type, found {} at '{}', which cannot be applied to a type",
+                    typ, expr
+                )
+            }
+            TypeCheckError::EscapingTypeVariable(var, expr) => {
+                write!(
+                    f,
+                    "Type variable {} is free in the context of '{}', so it cannot be abstracted over",
+                    var, expr
+                )
+            }
+            TypeCheckError::NotAProduct(typ, expr) => {
+                write!(
+                    f,
+                    "Expected a product, found {} at '{}', which cannot be projected",
+                    typ, expr
+                )
+            }
+            TypeCheckError::NotASum(typ, expr) => {
+                write!(f, "Expected a sum, found {} at '{}'", typ, expr)
+            }
+            TypeCheckError::MissingSumType(expr) => {
+                write!(
+                    f,
+                    "Missing type of injection: '{}', e.g. inl^(A + B) a (use -i to infer it)",
+                    expr
+                )
+            }
+            TypeCheckError::MissingAbsurdType(expr) => {
+                write!(
+                    f,
+                    "Missing type of absurd: '{}', e.g. absurd^A e (use -i to infer it)",
+                    expr
+                )
+            }
+            TypeCheckError::CannotHaveType(typ, expr) => {
+                write!(
+                    f,
+                    "Expected {}, found '{}', which cannot have that type",
+                    typ, expr
+                )
+            }
+            TypeCheckError::NoFixedPoint(typ, expr) => {
+                write!(
+                    f,
+                    "Expected a function of type A -> A, found {} at '{}', which has no fixed point",
+                    typ, expr
+                )
             }
         }
     }
 }
 
-type TypeCheckResult = std::result::Result<(), TypeCheckError>;
-
-/// Returns Ok(()) if the judgement is well-typed, Err(TypeCheckError) otherwise.
-fn _type_check(judgement: &Judgement) -> TypeCheckResult {
-    // Extract expression and type
-    let Judgement::Judgement(expr, typ) = judgement;
+impl TypeCheckError<'_> {
+    /// The identifier of the error in the JSON output
+    fn code(&self) -> &'static str {
+        match self {
+            TypeCheckError::UnboundVariable(_) => "unbound_variable",
+            TypeCheckError::MismatchedTypes(_, _, _) => "mismatched_types",
+            TypeCheckError::MismatchedArgument(_, _, _) => "mismatched_argument",
+            TypeCheckError::MismatchedJudgement(_, _) => "mismatched_judgement",
+            TypeCheckError::NotAFunction(_, _) => "not_a_function",
+            TypeCheckError::MissingAnnotation(_, _) => "missing_annotation",
+            TypeCheckError::NotPolymorphic(_, _) => "not_polymorphic",
+            TypeCheckError::EscapingTypeVariable(_, _) => "escaping_type_variable",
+            TypeCheckError::NotAProduct(_, _) => "not_a_product",
+            TypeCheckError::NotASum(_, _) => "not_a_sum",
+            TypeCheckError::MissingSumType(_) => "missing_sum_type",
+            TypeCheckError::MissingAbsurdType(_) => "missing_absurd_type",
+            TypeCheckError::CannotHaveType(_, _) => "cannot_have_type",
+            TypeCheckError::NoFixedPoint(_, _) => "no_fixed_point",
+        }
+    }
 
-    // Check if free variables (unknown types) are present, and count types
-    let mut free = HashSet::new();
-    let mut typeset: HashSet<String> = HashSet::new();
-    free_vars(expr, &mut free, &mut HashSet::new(), &mut typeset);
-    dbg!(&free, &typeset);
+    /// The error as it is reported in the JSON output, with the part of the expression it is at underlined
+    /// like the text output does (none for a judgement with the wrong type, which is not about a part of the expression). \
+    /// The column of the input is unknown, the expression does not know where it was in the input
+    fn diagnostic(&self, root: &Expression, idx: usize) -> Diagnostic {
+        let underline = self.blame().and_then(|(_, _, expr)| {
+            let (text, marks) = underline(root, expr);
+            let start = marks.find('^')?;
+            Some(Underline {
+                text,
+                column: start + 1,
+                length: marks.len() - start,
+            })
+        });
+        Diagnostic {
+            failure: Failure::Type,
+            code: self.code(),
+            message: self.to_string(),
+            line: idx + 1,
+            column: None,
+            underline,
+        }
+    }
 
-    if !free.is_empty() {
-        return Err(TypeCheckError::UnknownType(
-            free.into_iter().collect::<Vec<String>>().join(", "),
-        ));
+    /// The expected type and the type that was found, if the error has them,
+    /// and the expression the error is at (none for a judgement with the wrong type)
+    fn blame(&self) -> Option<(Option<&Type>, Option<&Type>, &Expression)> {
+        Some(match self {
+            TypeCheckError::MismatchedJudgement(_, _) => return None,
+            TypeCheckError::MismatchedTypes(expected, found, expr)
+            | TypeCheckError::MismatchedArgument(expected, found, expr) => {
+                (Some(expected), Some(found), expr)
+            }
+            TypeCheckError::NotAFunction(found, expr)
+            | TypeCheckError::NotPolymorphic(found, expr)
+            | TypeCheckError::NotAProduct(found, expr)
+            | TypeCheckError::NotASum(found, expr)
+            | TypeCheckError::NoFixedPoint(found, expr) => (None, Some(found), expr),
+            TypeCheckError::CannotHaveType(expected, expr) => (Some(expected), None, expr),
+            TypeCheckError::UnboundVariable(expr)
+            | TypeCheckError::MissingAnnotation(_, expr)
+            | TypeCheckError::EscapingTypeVariable(_, expr)
+            | TypeCheckError::MissingSumType(expr)
+            | TypeCheckError::MissingAbsurdType(expr) => (None, None, expr),
+        })
     }
 
-    // Check if all types are known
-    match check_judgement_type(typ, &typeset) {
-        Ok(_) => Ok(()),
-        Err(err_code) => Err(err_code),
+    /// The lines printed below the error: the expression with the part the error is at underlined,
+    /// or for a judgement with the wrong type, both types with the parts where they differ underlined
+    fn explanation(&self, root: &Expression) -> String {
+        let Some((expected, found, expr)) = self.blame() else {
+            let TypeCheckError::MismatchedJudgement(expected, found) = self else {
+                unreachable!("only a judgement with the wrong type has no expression to blame")
+            };
+            let (expected_text, expected_marks) = type_diff(expected, found);
+            let (found_text, found_marks) = type_diff(found, expected);
+            return format!(
+                "    expected: {}\n              {}\n    found:    {}\n              {}\n",
+                expected_text,
+                expected_marks.trim_end(),
+                found_text,
+                found_marks.trim_end()
+            );
+        };
+        let (text, marks) = underline(root, expr);
+        if marks.is_empty() {
+            return String::new();
+        }
+        // the types are printed after the underline, when there is one
+        let label = match (expected, found) {
+            (Some(expected), Some(found)) => format!(" expected {}, found {}", expected, found),
+            (Some(expected), None) => format!(" expected {}", expected),
+            (None, Some(found)) => format!(" found {}", found),
+            (None, None) => String::new(),
+        };
+        format!("    {}\n    {}{}\n", text, marks, label)
     }
 }
 
-// borrowed from ass2, modified to also keep track of types
-fn free_vars(
-    expression: &Expression,
-    free: &mut HashSet<String>,
-    abstr_vars: &mut HashSet<String>,
-    typeset: &mut HashSet<String>,
-) {
-    match expression {
+type TypeCheckResult<'a, T> = std::result::Result<T, TypeCheckError<'a>>;
+
+/// Synthesise the type of an expression in the given context,
+/// with the derivation of the typing judgement
+///
+/// Var: x has the type it is bound with \
+/// Abs: if e : B with x : A, then λx^A.e : A -> B \
+/// App: if f : A -> B and e : A, then f e : B \
+/// Let: if e1 : A, and e2 : B with x : A, then let x = e1 in e2 : B \
+/// TAbs: if e : T, and X is not free in the context, then ΛX.e : ∀X. T \
+/// TApp: if e : ∀X. T, then e [S] : T[X := S] \
+/// Unit: () : Unit \
+/// Pair: if e1 : A and e2 : B, then (e1, e2) : A * B \
+/// Fst/Snd: if e : A * B, then fst e : A and snd e : B \
+/// Inl/Inr: if e : A, then inl^(A + B) e : A + B, and if e : B, then inr^(A + B) e : A + B \
+/// Case: if e : A + B, e1 : C with x : A, and e2 : C with y : B,
+/// then case e of inl x => e1 | inr y => e2 : C \
+/// Anno: if e checks against T, then (e : T) : T \
+/// Zero: 0 : Nat \
+/// Succ/Pred: if e : Nat, then succ e : Nat and pred e : Nat \
+/// IsZero: if e : Nat, then iszero e : Bool \
+/// True/False: true : Bool and false : Bool \
+/// If: if e1 : Bool, e2 : A and e3 : A, then if e1 then e2 else e3 : A \
+/// Fix: if e : A -> A, then fix e : A \
+/// Absurd: if e : ⊥, then absurd^A e : A
+fn synthesise<'a>(expr: &'a Expression, context: &mut Context) -> TypeCheckResult<'a, Derivation> {
+    let (rule, typ, premises) = match expr {
+        Expression::Variable(name) => match context.iter().rev().find(|(var, _)| var == name) {
+            Some((_, typ)) => (Rule::Var, typ.clone(), Vec::new()),
+            None => return Err(TypeCheckError::UnboundVariable(expr)),
+        },
+        Expression::Abstraction(var, typ, body) => {
+            let Some(typ) = typ else {
+                return Err(TypeCheckError::MissingAnnotation(var.clone(), expr));
+            };
+            context.push((var.clone(), *typ.clone()));
+            let body = synthesise(body, context);
+            context.pop();
+            let body = body?;
+            let typ = Type::Function(typ.clone(), Box::new(body.typ.clone()));
+            (Rule::Abs, typ, vec![body])
+        }
         Expression::Application(lexpr, rexpr) => {
-            free_vars(lexpr, free, abstr_vars, typeset);
-            free_vars(rexpr, free, abstr_vars, typeset);
+            let function = synthesise(lexpr, context)?;
+            match &function.typ {
+                Type::Function(domain, codomain) => {
+                    // the type of the argument is known, so it is checked against it
+                    let argument =
+                        check(rexpr, domain, context).map_err(|err_code| match err_code {
+                            // the argument as a whole has the wrong type, rather than a part of it
+                            TypeCheckError::MismatchedTypes(expected, found, at)
+                                if std::ptr::eq(at, rexpr.as_ref()) =>
+                            {
+                                TypeCheckError::MismatchedArgument(expected, found, at)
+                            }
+                            err_code => err_code,
+                        })?;
+                    let typ = *codomain.clone();
+                    (Rule::App, typ, vec![function, argument])
+                }
+                typ => return Err(TypeCheckError::NotAFunction(typ.clone(), lexpr)),
+            }
         }
-        Expression::Abstraction(var, typ, body) => {
-            // If this abstraction adds the variable, also remove it.
-            if abstr_vars.insert(var.clone()) {
-                free_vars(body, free, abstr_vars, typeset);
-                abstr_vars.remove(var);
-            } else {
-                // the variable is already in the set, so should not be removed
-                free_vars(body, free, abstr_vars, typeset);
+        Expression::Let(var, bound, body) => {
+            let bound = synthesise(bound, context)?;
+            context.push((var.clone(), bound.typ.clone()));
+            let body = synthesise(body, context);
+            context.pop();
+            let body = body?;
+            let typ = body.typ.clone();
+            (Rule::Let, typ, vec![bound, body])
+        }
+        Expression::TypeAbstraction(var, body) => {
+            if context.iter().any(|(_, typ)| is_free_in(var, typ)) {
+                return Err(TypeCheckError::EscapingTypeVariable(var.clone(), expr));
             }
-
-            // add the type to the set of types
-            collect_types(typ, typeset);
+            let body = synthesise(body, context)?;
+            let typ = Type::ForAll(var.clone(), Box::new(body.typ.clone()));
+            (Rule::TAbs, typ, vec![body])
         }
-        Expression::Variable(varname) => {
-            if !abstr_vars.contains(varname) {
-                free.insert(varname.clone());
+        Expression::TypeApplication(lexpr, argument) => {
+            let function = synthesise(lexpr, context)?;
+            match &function.typ {
+                Type::ForAll(var, body) => {
+                    let typ = substitute_type(body, var, argument);
+                    (Rule::TApp, typ, vec![function])
+                }
+                typ => return Err(TypeCheckError::NotPolymorphic(typ.clone(), lexpr)),
             }
         }
-    }
-}
+        Expression::Unit => (Rule::Unit, Type::Unit, Vec::new()),
+        Expression::Pair(left, right) => {
+            let left = synthesise(left, context)?;
+            let right = synthesise(right, context)?;
+            let typ = Type::Product(Box::new(left.typ.clone()), Box::new(right.typ.clone()));
+            (Rule::Pair, typ, vec![left, right])
+        }
+        Expression::First(operand) | Expression::Second(operand) => {
+            let pair = synthesise(operand, context)?;
+            let Type::Product(left_type, right_type) = &pair.typ else {
+                return Err(TypeCheckError::NotAProduct(pair.typ, operand));
+            };
+            match expr {
+                Expression::First(_) => (Rule::Fst, *left_type.clone(), vec![pair]),
+                _ => (Rule::Snd, *right_type.clone(), vec![pair]),
+            }
+        }
+        Expression::Left(typ, inner) | Expression::Right(typ, inner) => {
+            let Some(typ) = typ else {
+                return Err(TypeCheckError::MissingSumType(expr));
+            };
+            let Type::Sum(left_type, right_type) = typ.as_ref() else {
+                return Err(TypeCheckError::NotASum(*typ.clone(), expr));
+            };
+            let (rule, expected) = match expr {
+                Expression::Left(_, _) => (Rule::Inl, left_type),
+                _ => (Rule::Inr, right_type),
+            };
+            let inner = check(inner, expected, context)?;
+            (rule, *typ.clone(), vec![inner])
+        }
+        Expression::Case(cased, left, left_branch, right, right_branch) => {
+            let scrutinee = synthesise(cased, context)?;
+            let Type::Sum(left_type, right_type) = &scrutinee.typ else {
+                return Err(TypeCheckError::NotASum(scrutinee.typ, cased));
+            };
+
+            context.push((left.clone(), *left_type.clone()));
+            let left_branch = synthesise(left_branch, context);
+            context.pop();
+            let left_branch = left_branch?;
 
-/// Collects all types in a type, and adds them to the typeset.
-fn collect_types(typ: &Type, typeset: &mut HashSet<String>) {
-    match typ {
-        Type::Function(t1, t2) => {
-            collect_types(t1, typeset);
-            collect_types(t2, typeset);
+            // both branches have the type of the whole case
+            context.push((right.clone(), *right_type.clone()));
+            let right_branch = check(right_branch, &left_branch.typ, context);
+            context.pop();
+            let right_branch = right_branch?;
+            let typ = left_branch.typ.clone();
+            (Rule::Case, typ, vec![scrutinee, left_branch, right_branch])
         }
-        Type::Variable(t) => {
-            typeset.insert(t.clone());
+        Expression::Ascription(inner, typ) => {
+            let inner = check(inner, typ, context)?;
+            (Rule::Anno, *typ.clone(), vec![inner])
         }
-    }
+        Expression::Zero => (Rule::Zero, Type::Nat, Vec::new()),
+        Expression::Succ(inner) | Expression::Pred(inner) | Expression::IsZero(inner) => {
+            let inner = check(inner, &Type::Nat, context)?;
+            match expr {
+                Expression::Succ(_) => (Rule::Succ, Type::Nat, vec![inner]),
+                Expression::Pred(_) => (Rule::Pred, Type::Nat, vec![inner]),
+                _ => (Rule::IsZero, Type::Bool, vec![inner]),
+            }
+        }
+        Expression::True => (Rule::True, Type::Bool, Vec::new()),
+        Expression::False => (Rule::False, Type::Bool, Vec::new()),
+        Expression::If(condition, then_branch, else_branch) => {
+            let condition = check(condition, &Type::Bool, context)?;
+            // both branches have the type of the whole if
+            let then_branch = synthesise(then_branch, context)?;
+            let else_branch = check(else_branch, &then_branch.typ, context)?;
+            let typ = then_branch.typ.clone();
+            (Rule::If, typ, vec![condition, then_branch, else_branch])
+        }
+        Expression::Fix(fixed) => {
+            let function = synthesise(fixed, context)?;
+            match &function.typ {
+                Type::Function(domain, codomain) if alpha_equivalent(domain, codomain) => {
+                    let typ = *domain.clone();
+                    (Rule::Fix, typ, vec![function])
+                }
+                typ => return Err(TypeCheckError::NoFixedPoint(typ.clone(), fixed)),
+            }
+        }
+        Expression::Absurd(typ, inner) => {
+            let Some(typ) = typ else {
+                return Err(TypeCheckError::MissingAbsurdType(expr));
+            };
+            let inner = check(inner, &Type::Empty, context)?;
+            (Rule::Absurd, *typ.clone(), vec![inner])
+        }
+    };
+    Ok(Derivation {
+        rule,
+        context: context.clone(),
+        expr: expr.clone(),
+        typ,
+        premises,
+    })
 }
 
-/// Check all types in a judgement's type, and return an error if any of them are unknown.
-fn check_judgement_type(typ: &Type, typeset: &HashSet<String>) -> TypeCheckResult {
-    match typ {
-        Type::Function(t1, t2) => {
-            check_judgement_type(t1, typeset)?;
-            check_judgement_type(t2, typeset)?;
+/// Check the expression against the expected type in the given context,
+/// with the derivation of the typing judgement. \
+/// Where the type is known, the types of bound variables and injections can be left out:
+///
+/// Abs: if e checks against B with x : A, then λx.e checks against A -> B \
+/// Inl/Inr: if e checks against A, then inl e checks against A + B, and if e checks against B, then inr e does \
+/// Pair: if e1 checks against A and e2 against B, then (e1, e2) checks against A * B \
+/// TAbs: if e checks against T, and X is not free in the context, then ΛX.e checks against ∀X. T \
+/// Let, Case and If: the body and the branches are checked against the type \
+/// Fix: if e checks against A -> A, then fix e checks against A \
+/// Absurd: if e checks against ⊥, then absurd e checks against any type \
+/// Any other expression (or an annotation that is not the expected type) synthesises its type,
+/// which has to be the expected type
+fn check<'a>(
+    expr: &'a Expression,
+    expected: &Type,
+    context: &mut Context,
+) -> TypeCheckResult<'a, Derivation> {
+    let (rule, premises) = match (expr, expected) {
+        (Expression::Abstraction(var, typ, body), Type::Function(domain, codomain))
+            if typ.as_ref().is_none_or(|typ| alpha_equivalent(typ, domain)) =>
+        {
+            context.push((var.clone(), *domain.clone()));
+            let body = check(body, codomain, context);
+            context.pop();
+            (Rule::Abs, vec![body?])
         }
-        Type::Variable(t) => {
-            if !typeset.contains(t) {
-                return Err(TypeCheckError::MismatchedTypes(t.clone()));
+        (
+            Expression::Left(typ, inner) | Expression::Right(typ, inner),
+            Type::Sum(left_type, right_type),
+        ) if typ
+            .as_ref()
+            .is_none_or(|typ| alpha_equivalent(typ, expected)) =>
+        {
+            match expr {
+                Expression::Left(_, _) => (Rule::Inl, vec![check(inner, left_type, context)?]),
+                _ => (Rule::Inr, vec![check(inner, right_type, context)?]),
             }
         }
-    }
+        (
+            Expression::Abstraction(_, None, _)
+            | Expression::Left(None, _)
+            | Expression::Right(None, _),
+            _,
+        ) => {
+            return Err(TypeCheckError::CannotHaveType(expected.clone(), expr));
+        }
+        (Expression::Pair(left, right), Type::Product(left_type, right_type)) => {
+            let left = check(left, left_type, context)?;
+            let right = check(right, right_type, context)?;
+            (Rule::Pair, vec![left, right])
+        }
+        (Expression::TypeAbstraction(var, body), Type::ForAll(bound, typ)) => {
+            if context.iter().any(|(_, typ)| is_free_in(var, typ)) {
+                return Err(TypeCheckError::EscapingTypeVariable(var.clone(), expr));
+            }
+            // ∀Y. T is ∀X. T[Y := X]
+            let typ = substitute_type(typ, bound, &Type::Variable(var.clone()));
+            (Rule::TAbs, vec![check(body, &typ, context)?])
+        }
+        (Expression::Let(var, bound, body), _) => {
+            let bound = synthesise(bound, context)?;
+            context.push((var.clone(), bound.typ.clone()));
+            let body = check(body, expected, context);
+            context.pop();
+            (Rule::Let, vec![bound, body?])
+        }
+        (Expression::Case(cased, left, left_branch, right, right_branch), _) => {
+            let scrutinee = synthesise(cased, context)?;
+            let Type::Sum(left_type, right_type) = &scrutinee.typ else {
+                return Err(TypeCheckError::NotASum(scrutinee.typ, cased));
+            };
+            context.push((left.clone(), *left_type.clone()));
+            let left_branch = check(left_branch, expected, context);
+            context.pop();
+            context.push((right.clone(), *right_type.clone()));
+            let right_branch = check(right_branch, expected, context);
+            context.pop();
+            (Rule::Case, vec![scrutinee, left_branch?, right_branch?])
+        }
+        (Expression::If(condition, then_branch, else_branch), _) => {
+            let condition = check(condition, &Type::Bool, context)?;
+            let then_branch = check(then_branch, expected, context)?;
+            let else_branch = check(else_branch, expected, context)?;
+            (Rule::If, vec![condition, then_branch, else_branch])
+        }
+        (Expression::Fix(function), _) => {
+            let typ = Type::Function(Box::new(expected.clone()), Box::new(expected.clone()));
+            (Rule::Fix, vec![check(function, &typ, context)?])
+        }
+        (Expression::Absurd(typ, inner), _)
+            if typ
+                .as_ref()
+                .is_none_or(|typ| alpha_equivalent(typ, expected)) =>
+        {
+            (Rule::Absurd, vec![check(inner, &Type::Empty, context)?])
+        }
+        _ => {
+            let derivation = synthesise(expr, context)?;
+            if !alpha_equivalent(&derivation.typ, expected) {
+                return Err(TypeCheckError::MismatchedTypes(
+                    expected.clone(),
+                    derivation.typ,
+                    expr,
+                ));
+            }
+            return Ok(derivation);
+        }
+    };
+    Ok(Derivation {
+        rule,
+        context: context.clone(),
+        expr: expr.clone(),
+        typ: expected.clone(),
+        premises,
+    })
+}
+
+/// Returns the derivation of the judgement if it is derivable, Err(TypeCheckError) otherwise.
+fn _type_check(judgement: &Judgement) -> TypeCheckResult<'_, Derivation> {
+    // Extract expression and type
+    let Judgement::Judgement(context, expr, typ) = judgement;
+
+    // the free variables of the expression are typed by the context of the judgement,
+    // and the expression is checked against the type of the judgement
+    let derivation = check(expr, typ, &mut context.clone()).map_err(|err_code| match err_code {
+        // the expression as a whole has the wrong type, rather than a part of it
+        TypeCheckError::MismatchedTypes(expected, found, at) if std::ptr::eq(at, expr.as_ref()) => {
+            TypeCheckError::MismatchedJudgement(expected, found)
+        }
+        err_code => err_code,
+    })?;
+    dbg!(&derivation.typ);
+    Ok(derivation)
+}
 
-    Ok(())
+/// Returns the derivation of the type of an expression in the context, Err(TypeCheckError) if it has none.
+fn _synthesise_type<'a>(
+    expr: &'a Expression,
+    context: &Context,
+) -> TypeCheckResult<'a, Derivation> {
+    let derivation = synthesise(expr, &mut context.clone())?;
+    dbg!(&derivation.typ);
+    Ok(derivation)
 }
 
-pub(super) fn type_check(judgement: &Judgement, idx: usize) {
+pub(super) fn type_check(judgement: &Judgement, idx: usize) -> Derivation {
+    let Judgement::Judgement(_, expr, _) = judgement;
     match _type_check(judgement) {
-        Ok(_) => (),
+        Ok(derivation) => derivation,
         Err(err_code) => {
             eprintln!(
-                "Invalid judgement [{}] caught during parsing on line {}!",
+                "Invalid judgement [{}] caught during typechecking on line {}!",
                 err_code,
                 idx + 1
             );
+            eprint!("{}", err_code.explanation(expr));
 
-            std::process::exit(1);
+            exit(Failure::Type);
         }
     }
 }
 
+/// Typecheck the judgement, returning the error if it is not derivable. \
+/// Only used for the JSON output, where every line is reported even if one fails
+pub(crate) fn json_type_check(
+    judgement: &Judgement,
+    idx: usize,
+) -> std::result::Result<(), Diagnostic> {
+    let Judgement::Judgement(_, expr, _) = judgement;
+    _type_check(judgement)
+        .map(|_| ())
+        .map_err(|err_code| err_code.diagnostic(expr, idx))
+}
+
+/// Synthesise the type of an annotated expression in the context, returning the error if it has none. \
+/// Only used for the JSON output, where every line is reported even if one fails
+pub(crate) fn json_synthesise_type(
+    expr: &Expression,
+    context: &Context,
+    idx: usize,
+) -> std::result::Result<Type, Diagnostic> {
+    _synthesise_type(expr, context)
+        .map(|derivation| derivation.typ)
+        .map_err(|err_code| err_code.diagnostic(expr, idx))
+}
+
 pub(crate) fn bench_type_check(judgement: &Judgement) {
     _type_check(judgement).unwrap();
 }
 
 pub(crate) fn manual_type_check(judgement: &Judgement) -> bool {
+    let Judgement::Judgement(_, expr, _) = judgement;
     match _type_check(judgement) {
         Ok(_) => true,
         Err(err_code) => {
@@ -144,7 +632,182 @@ pub(crate) fn manual_type_check(judgement: &Judgement) -> bool {
                 "Invalid judgement [{}] caught during typechecking!",
                 err_code
             );
+            print!("{}", err_code.explanation(expr));
             false
         }
     }
 }
+
+/// Synthesise the type of an expression in the given context. \
+/// If the expression has no type, prints the error and returns None. \
+/// Only used for manual mode (e.g. the function a tactic applies), where we want to keep going even if there is an error
+pub(crate) fn manual_synthesise_type(expr: &Expression, context: &Context) -> Option<Type> {
+    match synthesise(expr, &mut context.clone()) {
+        Ok(derivation) => Some(derivation.typ),
+        Err(err_code) => {
+            println!(
+                "Invalid expression [{}] caught during typechecking!",
+                err_code
+            );
+            print!("{}", err_code.explanation(expr));
+            None
+        }
+    }
+}
+
+/// Synthesise the type of an annotated expression in the context (empty for a closed expression)
+/// If the expression has no type, prints an error and exits the program
+///
+/// # Arguments
+/// * `expr` - The expression to synthesise the type of
+/// * `context` - The types of the free variables of the expression
+/// * `idx` - The index of the line the expression is on (for error printing)
+///
+/// # Returns
+/// The derivation of the type of the expression
+///
+/// # Error
+/// "Invalid expression [{err_code}] caught during typechecking on line {idx}!"
+pub(crate) fn synthesise_type(expr: &Expression, context: &Context, idx: usize) -> Derivation {
+    match _synthesise_type(expr, context) {
+        Ok(derivation) => derivation,
+        Err(err_code) => {
+            eprintln!(
+                "Invalid expression [{}] caught during typechecking on line {}!",
+                err_code,
+                idx + 1
+            );
+            eprint!("{}", err_code.explanation(expr));
+            exit(Failure::Type);
+        }
+    }
+}
+
+/// Typecheck a term that the expression of the judgement reduced to,
+/// it should still have the type of the judgement (subject reduction)
+/// If it does not, prints an error and exits the program
+///
+/// # Arguments
+/// * `judgement` - The judgement with the reduced term, and the type of the original expression
+/// * `step` - The number of reduction steps it took to get to the term (for error printing)
+/// * `idx` - The index of the line the expression is on (for error printing)
+///
+/// # Error
+/// "Subject reduction failed [{err_code}] after {step} reduction steps on line {idx}!"
+pub(crate) fn subject_reduction_check(judgement: &Judgement, step: usize, idx: usize) {
+    let Judgement::Judgement(_, expr, _) = judgement;
+    if let Err(err_code) = _type_check(judgement) {
+        eprintln!(
+            "Subject reduction failed [{}] after {} reduction steps on line {}!",
+            err_code,
+            step,
+            idx + 1
+        );
+        eprint!("{}", err_code.explanation(expr));
+        exit(Failure::Type);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parser::{manual_parse, manual_parse_expression},
+        tokenizer::manual_tokenize,
+    };
+
+    fn expression(input: &str) -> Expression {
+        manual_parse_expression(&manual_tokenize(input).unwrap()).unwrap()
+    }
+
+    fn judgement(input: &str) -> Judgement {
+        manual_parse(&manual_tokenize(input).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn judgement_is_checked_against_the_synthesised_type() {
+        assert!(_type_check(&judgement("λx^A.x : A -> A")).is_ok());
+        // the shapes match, but the types do not
+        assert!(matches!(
+            _type_check(&judgement("(\\x^A x) : B -> B")),
+            Err(TypeCheckError::MismatchedJudgement(expected, found))
+                if expected.to_string() == "B -> B" && found.to_string() == "A -> A"
+        ));
+    }
+
+    #[test]
+    fn argument_mismatch_blames_the_argument() {
+        let expr = expression("(λx^A.x) (λy^B.y)");
+        let err_code = _synthesise_type(&expr, &Context::new()).unwrap_err();
+        assert!(matches!(
+            &err_code,
+            TypeCheckError::MismatchedArgument(expected, found, at)
+                if expected.to_string() == "A"
+                    && found.to_string() == "B -> B"
+                    && at.to_string() == "λy^B.y"
+        ));
+        // the argument is underlined, with both types
+        assert_eq!(
+            err_code.explanation(&expr),
+            "    (λx^A.x) λy^B.y\n             ^^^^^^ expected A, found B -> B\n"
+        );
+    }
+
+    #[test]
+    fn applying_a_non_function_gives_its_type() {
+        let context = vec![("x".to_string(), Type::Variable("A".to_string()))];
+        assert!(matches!(
+            _synthesise_type(&expression("x x"), &context),
+            Err(TypeCheckError::NotAFunction(found, at))
+                if found.to_string() == "A" && at.to_string() == "x"
+        ));
+    }
+
+    #[test]
+    fn polymorphic_substitution_avoids_capture() {
+        // [X := Y] renames the bound Y, so the result is ∀Y1. Y -> Y1 -> Y and not ∀Y. Y -> Y -> Y
+        let expr = expression("(ΛX.ΛY.λx^X.λy^Y.x) [Y]");
+        let typ = _synthesise_type(&expr, &Context::new()).unwrap().typ;
+        let var = |name: &str| Box::new(Type::Variable(name.to_string()));
+        let expected = |bound: &str| {
+            Type::ForAll(
+                bound.to_string(),
+                Box::new(Type::Function(
+                    var("Y"),
+                    Box::new(Type::Function(var(bound), var("Y"))),
+                )),
+            )
+        };
+        assert!(alpha_equivalent(&typ, &expected("Z")));
+        assert!(!alpha_equivalent(&typ, &expected("Y")));
+    }
+
+    #[test]
+    fn type_variable_free_in_the_context_is_not_abstracted() {
+        let context = vec![("x".to_string(), Type::Variable("X".to_string()))];
+        assert!(matches!(
+            _synthesise_type(&expression("ΛX.x"), &context),
+            Err(TypeCheckError::EscapingTypeVariable(var, _)) if var == "X"
+        ));
+        assert!(_synthesise_type(&expression("ΛY.x"), &context).is_ok());
+    }
+
+    #[test]
+    fn abstraction_without_dot_ends_at_its_body() {
+        // `\x^A \y^(A->B) y x` is `(λx^A.λy^(A -> B).y) x`, so the last x is free
+        let expr = expression("\\x^A \\y^(A->B) y x");
+        assert_eq!(expr, expression("(λx^A.λy^(A -> B).y) x"));
+        assert!(matches!(
+            _synthesise_type(&expr, &Context::new()),
+            Err(TypeCheckError::UnboundVariable(Expression::Variable(var))) if var == "x"
+        ));
+        let dotted = expression("\\x^A.\\y^(A->B).y x");
+        assert_eq!(
+            _synthesise_type(&dotted, &Context::new())
+                .unwrap()
+                .typ
+                .to_string(),
+            "A -> (A -> B) -> B"
+        );
+    }
+}
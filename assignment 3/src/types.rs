@@ -0,0 +1,129 @@
+// L.A. (Luca) Verheul - S3704041
+// Mon 19 Oct 2026
+
+use crate::parser::Type;
+
+/// Rebuild a binary type (function, product or sum) with new operands
+pub(crate) fn rebuild(typ: &Type, left: Type, right: Type) -> Type {
+    match typ {
+        Type::Product(_, _) => Type::Product(Box::new(left), Box::new(right)),
+        Type::Sum(_, _) => Type::Sum(Box::new(left), Box::new(right)),
+        _ => Type::Function(Box::new(left), Box::new(right)),
+    }
+}
+
+/// Collect the free type variables of the type, i.e. those not bound by a ∀
+pub(crate) fn free_type_variables(typ: &Type, vars: &mut Vec<String>) {
+    match typ {
+        Type::Variable(name) => {
+            if !vars.contains(name) {
+                vars.push(name.clone());
+            }
+        }
+        Type::Function(t1, t2) | Type::Product(t1, t2) | Type::Sum(t1, t2) => {
+            free_type_variables(t1, vars);
+            free_type_variables(t2, vars);
+        }
+        Type::ForAll(var, body) => {
+            let mut inner = Vec::new();
+            free_type_variables(body, &mut inner);
+            for name in inner {
+                if name != *var && !vars.contains(&name) {
+                    vars.push(name);
+                }
+            }
+        }
+        Type::Unknown(_) | Type::Unit | Type::Nat | Type::Bool | Type::Empty => {}
+    }
+}
+
+/// Whether the type variable occurs free in the type
+pub(crate) fn is_free_in(var: &str, typ: &Type) -> bool {
+    let mut vars = Vec::new();
+    free_type_variables(typ, &mut vars);
+    vars.iter().any(|name| name == var)
+}
+
+/// A variant of the name that is not in the list, e.g. X1, X2, ...
+fn fresh_name(name: &str, taken: &[String]) -> String {
+    (1..)
+        .map(|n| format!("{name}{n}"))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap()
+}
+
+/// Capture-avoiding substitution T[X := S]: replace the free occurrences of the type variable by the replacement. \
+/// A bound variable that occurs free in the replacement is renamed first,
+/// so (∀Y. X -> Y)[X := Y] = ∀Y1. Y -> Y1 and not ∀Y. Y -> Y
+pub(crate) fn substitute_type(typ: &Type, var: &str, replacement: &Type) -> Type {
+    match typ {
+        Type::Variable(name) if name == var => replacement.clone(),
+        Type::Variable(_)
+        | Type::Unknown(_)
+        | Type::Unit
+        | Type::Nat
+        | Type::Bool
+        | Type::Empty => typ.clone(),
+        Type::Function(t1, t2) | Type::Product(t1, t2) | Type::Sum(t1, t2) => rebuild(
+            typ,
+            substitute_type(t1, var, replacement),
+            substitute_type(t2, var, replacement),
+        ),
+        // the variable is shadowed, so it has no free occurrences in the body
+        Type::ForAll(bound, _) if bound == var => typ.clone(),
+        Type::ForAll(bound, body) => {
+            if is_free_in(bound, replacement) && is_free_in(var, body) {
+                let mut taken = Vec::new();
+                free_type_variables(replacement, &mut taken);
+                free_type_variables(body, &mut taken);
+                taken.push(var.to_string());
+                let renamed = fresh_name(bound, &taken);
+                let body = substitute_type(body, bound, &Type::Variable(renamed.clone()));
+                Type::ForAll(renamed, Box::new(substitute_type(&body, var, replacement)))
+            } else {
+                Type::ForAll(
+                    bound.clone(),
+                    Box::new(substitute_type(body, var, replacement)),
+                )
+            }
+        }
+    }
+}
+
+/// Whether the types are equal up to the names of their bound type variables,
+/// e.g. ∀X. X -> X and ∀Y. Y -> Y
+pub(crate) fn alpha_equivalent(t1: &Type, t2: &Type) -> bool {
+    alpha_equivalent_in(t1, t2, &mut Vec::new())
+}
+
+/// `bound` are the pairs of type variables bound by the enclosing quantifiers, innermost last
+fn alpha_equivalent_in(t1: &Type, t2: &Type, bound: &mut Vec<(String, String)>) -> bool {
+    match (t1, t2) {
+        (Type::Variable(a), Type::Variable(b)) => {
+            let left = bound.iter().rposition(|(x, _)| x == a);
+            let right = bound.iter().rposition(|(_, y)| y == b);
+            match (left, right) {
+                (Some(i), Some(j)) => i == j,
+                (None, None) => a == b,
+                _ => false,
+            }
+        }
+        (Type::Function(a1, b1), Type::Function(a2, b2))
+        | (Type::Product(a1, b1), Type::Product(a2, b2))
+        | (Type::Sum(a1, b1), Type::Sum(a2, b2)) => {
+            alpha_equivalent_in(a1, a2, bound) && alpha_equivalent_in(b1, b2, bound)
+        }
+        (Type::ForAll(x, body1), Type::ForAll(y, body2)) => {
+            bound.push((x.clone(), y.clone()));
+            let equivalent = alpha_equivalent_in(body1, body2, bound);
+            bound.pop();
+            equivalent
+        }
+        (Type::Unknown(n), Type::Unknown(m)) => n == m,
+        (Type::Unit, Type::Unit)
+        | (Type::Nat, Type::Nat)
+        | (Type::Bool, Type::Bool)
+        | (Type::Empty, Type::Empty) => true,
+        _ => false,
+    }
+}
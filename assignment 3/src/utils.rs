@@ -15,3 +15,103 @@ macro_rules! dbg {
         }
     };
 }
+
+/// The kind of failure the program exits with
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Failure {
+    /// The judgement is not derivable (or the term has no type)
+    Type,
+    /// The reduction did not reach a normal form within its bound
+    Reduce,
+    /// The input could not be tokenized
+    Lex,
+    /// The tokens could not be parsed
+    Parse,
+    /// Invalid command line arguments
+    Usage,
+    /// The input could not be read, e.g. a file that does not exist
+    Input,
+}
+
+impl Failure {
+    /// The exit code of the failure: 1, 2, 3, 4, 5 and 6 in the order above,
+    /// so a judgement that is not derivable exits with 1, like the assignment requires
+    pub(crate) fn code(&self) -> i32 {
+        match self {
+            Failure::Type => 1,
+            Failure::Reduce => 2,
+            Failure::Lex => 3,
+            Failure::Parse => 4,
+            Failure::Usage => 5,
+            Failure::Input => 6,
+        }
+    }
+}
+
+/// Exit the program with the exit code of the failure (the error is printed already)
+pub(crate) fn exit(failure: Failure) -> ! {
+    std::process::exit(failure.code())
+}
+
+/// The options and the file given to one of the single letter modes, e.g. `-n --erase file.txt`
+#[derive(Debug, Default)]
+pub(crate) struct ModeArguments {
+    /// The options that were given, with their value if they take one
+    pub(crate) options: Vec<(String, Option<String>)>,
+    /// The file to read, stdin if there is none
+    pub(crate) file: Option<String>,
+}
+
+impl ModeArguments {
+    /// Whether the option was given
+    pub(crate) fn has(&self, option: &str) -> bool {
+        self.options.iter().any(|(name, _)| name == option)
+    }
+}
+
+/// Print the error with a hint to the help of the mode, and exit with the usage exit code
+pub(crate) fn mode_usage_error(message: &str, mode: &str) -> ! {
+    eprintln!("lc: {}", message);
+    eprintln!("Try 'lc {} --help' for more information.", mode);
+    exit(Failure::Usage);
+}
+
+/// Parse the arguments after the mode flag `args[1]`: the `flags`, the `valued` options that take a value,
+/// and at most one file (`-` is stdin). \
+/// `-h` or `--help` prints the usage and exits, an unknown option or a second file is a usage error
+pub(crate) fn mode_arguments(
+    args: &[String],
+    usage: &str,
+    flags: &[&str],
+    valued: &[&str],
+) -> ModeArguments {
+    let mode = args[1].as_str();
+    let mut arguments = ModeArguments::default();
+    let mut files = Vec::new();
+    let mut rest = args.iter().skip(2);
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", usage);
+                std::process::exit(0);
+            }
+            flag if flags.contains(&flag) => arguments.options.push((arg.clone(), None)),
+            option if valued.contains(&option) => match rest.next() {
+                Some(value) => arguments.options.push((arg.clone(), Some(value.clone()))),
+                None => mode_usage_error(&format!("{} expects a value", option), mode),
+            },
+            option if option.starts_with('-') && option != "-" => {
+                mode_usage_error(&format!("unexpected option '{}'", option), mode)
+            }
+            _ => files.push(arg),
+        }
+    }
+    if let Some(extra) = files.get(1) {
+        mode_usage_error(&format!("unexpected argument '{}'", extra), mode);
+    }
+    arguments.file = files
+        .first()
+        .filter(|file| **file != "-")
+        .map(|file| file.to_string());
+    arguments
+}